@@ -1,7 +1,11 @@
+pub mod rpc;
+pub mod secure;
+
 use crate::core::data_structures::*;
+use crate::network::Network;
 use crate::wallet::{Wallet, SendRequest, Currency};
 use axum::{
-    extract::State,
+    extract::{Query, State},
     http::{Method, StatusCode},
     response::IntoResponse,
     routing::{get, post},
@@ -9,6 +13,7 @@ use axum::{
 };
 use std::sync::Arc;
 use std::collections::HashMap;
+use tokio::sync::RwLock;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::services::ServeDir;
 use tracing::{error, info};
@@ -18,6 +23,7 @@ use base64::Engine;
 #[derive(Clone)]
 pub struct ApiState {
     pub node: Arc<crate::core::DuxNetNode>,
+    pub secure_sessions: secure::SecureSessions,
 }
 
 pub async fn start_api_server(port: u16) -> Result<(), Box<dyn std::error::Error>> {
@@ -26,7 +32,10 @@ pub async fn start_api_server(port: u16) -> Result<(), Box<dyn std::error::Error
     // Create a mock node for the API (in a real app, this would be shared)
     let node = Arc::new(crate::core::DuxNetNode::new(8080).await?);
     
-    let state = ApiState { node };
+    let state = ApiState {
+        node,
+        secure_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
     
     let cors = CorsLayer::new()
         .allow_methods([Method::GET, Method::POST])
@@ -40,16 +49,27 @@ pub async fn start_api_server(port: u16) -> Result<(), Box<dyn std::error::Error
         .route("/api/escrow/create", post(create_escrow))
         .route("/api/reputation/:did", get(get_reputation))
         .route("/api/stats", get(get_stats))
+        .route("/api/rates", get(get_rates))
         .route("/api/wallet/info", get(get_wallet_info))
         .route("/api/wallet/balances", get(get_wallet_balances))
         .route("/api/wallet/addresses", get(get_wallet_addresses))
+        .route("/api/wallet/payment_request", post(create_payment_request))
+        .route("/api/wallet/payment_request/parse", get(parse_payment_request))
         .route("/api/wallet/send", post(send_funds))
         .route("/api/wallet/receive", post(receive_funds))
         .route("/api/wallet/transactions", get(get_transaction_history))
         .route("/api/wallet/transaction/:id", get(get_transaction_by_id))
         .route("/api/wallet/backup", get(backup_wallet))
         .route("/api/wallet/restore", post(restore_wallet))
+        .route("/api/wallet/backup_encrypted", post(backup_wallet_encrypted))
+        .route("/api/wallet/restore_encrypted", post(restore_wallet_encrypted))
         .route("/api/wallet/keys", get(get_wallet_keys))
+        .route("/api/wallet/swap/offer", post(swap_offer))
+        .route("/api/wallet/swap/accept", post(swap_accept))
+        .route("/api/wallet/swap/status/:id", get(swap_status))
+        .route("/api/owner/init_secure", post(secure::init_secure))
+        .route("/api/owner/encrypted", post(secure::encrypted))
+        .route("/rpc", post(rpc::rpc_handler))
         .route("/", get(serve_index))
         .route("/index.html", get(serve_index))
         .nest_service("/static", ServeDir::new("static"))
@@ -71,7 +91,7 @@ async fn serve_index() -> Result<Html<String>, StatusCode> {
 async fn get_status(State(state): State<ApiState>) -> impl IntoResponse {
     let node = &state.node;
     let reputation = node.get_reputation(&node.did_manager.did.id).await;
-    let peers = node.network.get_peers().await;
+    let peers = node.network.connected_peers().await;
     
     let status = NodeStatus {
         node_id: node.node_id.0.clone(),
@@ -159,10 +179,22 @@ async fn create_escrow(
     axum::Json(request): axum::Json<CreateEscrowRequest>,
 ) -> impl IntoResponse {
     let node = &state.node;
-    
     let service_id = ServiceId(request.service_id);
-    
-    match node.create_escrow_for_service(&service_id, request.seller_did, request.amount).await {
+
+    let result = match (request.usd_amount, request.currency) {
+        (Some(usd_amount), Some(currency)) => {
+            match usd_amount.parse::<rust_decimal::Decimal>() {
+                Ok(usd_amount) => {
+                    node.create_escrow_for_service_usd(&service_id, request.seller_did, usd_amount, parse_currency(&currency))
+                        .await
+                }
+                Err(e) => Err(anyhow::anyhow!("invalid usd_amount: {}", e)),
+            }
+        }
+        _ => node.create_escrow_for_service(&service_id, request.seller_did, request.amount).await,
+    };
+
+    match result {
         Ok(escrow_id) => axum::Json(CreateEscrowResponse {
             escrow_id,
             success: true,
@@ -197,11 +229,12 @@ async fn get_stats(State(state): State<ApiState>) -> impl IntoResponse {
     let node = &state.node;
     
     let dht_stats = node.dht.get_stats().await;
+    let dht_metrics = node.dht.get_metrics().await;
     let reputation_stats = node.reputation_system.get_stats().await;
     let escrow_stats = node.escrow_manager.get_stats().await;
     let task_stats = node.task_engine.get_stats().await;
     let network_stats = node.network.get_stats().await;
-    
+
     axum::Json(serde_json::json!({
         "dht": {
             "total_entries": dht_stats.total_entries,
@@ -210,6 +243,7 @@ async fn get_stats(State(state): State<ApiState>) -> impl IntoResponse {
             "reputation_entries": dht_stats.reputation_entries,
             "escrow_entries": dht_stats.escrow_entries,
         },
+        "dht_metrics": dht_metrics,
         "reputation": {
             "total_nodes": reputation_stats.total_nodes,
             "total_attestations": reputation_stats.total_attestations,
@@ -239,6 +273,24 @@ async fn get_stats(State(state): State<ApiState>) -> impl IntoResponse {
     }))
 }
 
+async fn get_rates(State(state): State<ApiState>) -> impl IntoResponse {
+    let node = &state.node;
+    match node.rate_cache.get_all_rates().await {
+        Ok(rates) => axum::Json(serde_json::json!({
+            "success": true,
+            "rates": rates,
+            "timestamp": get_current_timestamp(),
+        })),
+        Err(e) => {
+            error!("Failed to fetch rates: {}", e);
+            axum::Json(serde_json::json!({
+                "success": false,
+                "message": format!("Failed to fetch rates: {}", e)
+            }))
+        }
+    }
+}
+
 // Wallet API endpoints
 async fn get_wallet_info(State(state): State<ApiState>) -> impl IntoResponse {
     let node = &state.node;
@@ -268,7 +320,7 @@ async fn get_wallet_balances(State(state): State<ApiState>) -> impl IntoResponse
         formatted_balances.insert(currency.symbol().to_string(), currency.format_amount(amount));
     }
     
-    let total_usd = wallet.get_total_balance_usd();
+    let total_usd = wallet.get_total_balance_usd(node.price_oracle.as_ref());
     
     axum::Json(serde_json::json!({
         "success": true,
@@ -293,6 +345,70 @@ async fn get_wallet_addresses(State(state): State<ApiState>) -> impl IntoRespons
     }))
 }
 
+#[derive(serde::Deserialize)]
+struct CreatePaymentRequestBody {
+    currency: String,
+    amount: Option<u64>,
+    memo: Option<String>,
+}
+
+async fn create_payment_request(
+    State(state): State<ApiState>,
+    axum::Json(request): axum::Json<CreatePaymentRequestBody>,
+) -> impl IntoResponse {
+    let node = &state.node;
+    let currency = parse_currency(&request.currency);
+    let address = {
+        let wallet = node.wallet.read().await;
+        wallet.get_address(&currency)
+    };
+
+    let payment_request = crate::wallet::payment_request::PaymentRequest {
+        currency,
+        address,
+        amount: request.amount,
+        memo: request.memo,
+    };
+
+    match crate::wallet::payment_request::build_payload(&payment_request) {
+        Ok(payload) => axum::Json(serde_json::json!({
+            "success": true,
+            "uri": payload.uri,
+            "qr_data_uri": payload.qr_data_uri,
+        })),
+        Err(e) => {
+            error!("Failed to build payment request: {}", e);
+            axum::Json(serde_json::json!({
+                "success": false,
+                "message": format!("Failed to build payment request: {}", e)
+            }))
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct ParsePaymentRequestQuery {
+    uri: String,
+}
+
+async fn parse_payment_request(
+    Query(params): Query<ParsePaymentRequestQuery>,
+) -> impl IntoResponse {
+    match crate::wallet::payment_request::parse_uri(&params.uri) {
+        Ok(parsed) => axum::Json(serde_json::json!({
+            "success": true,
+            "from": parsed.address,
+            "amount": parsed.amount,
+            "currency": parsed.currency.symbol(),
+            "memo": parsed.memo,
+        })),
+        Err(e) => axum::Json(serde_json::json!({
+            "success": false,
+            "message": format!("Failed to parse payment request: {}", e)
+        })),
+    }
+}
+
 async fn send_funds(
     State(state): State<ApiState>,
     axum::Json(request): axum::Json<crate::wallet::SendRequest>,
@@ -326,8 +442,10 @@ async fn receive_funds(
     let amount = request["amount"].as_u64().unwrap_or(0);
     let currency_str = request["currency"].as_str().unwrap_or("USDC");
     let transaction_id = request["transaction_id"].as_str().unwrap_or("");
+    let fee = request["fee"].as_u64().unwrap_or(0);
     let signature = request["signature"].as_str().unwrap_or("");
-    
+    let public_key = request["public_key"].as_str().unwrap_or("");
+
     let currency = match currency_str {
         "BTC" => crate::wallet::Currency::BTC,
         "ETH" => crate::wallet::Currency::ETH,
@@ -337,15 +455,19 @@ async fn receive_funds(
         "DOGE" => crate::wallet::Currency::DOGE,
         _ => crate::wallet::Currency::USDC,
     };
-    
+
     let signature_bytes = match base64::engine::general_purpose::STANDARD.decode(signature) {
         Ok(bytes) => bytes,
         Err(_) => vec![],
     };
-    
+    let public_key_bytes = match base64::engine::general_purpose::STANDARD.decode(public_key) {
+        Ok(bytes) => bytes,
+        Err(_) => vec![],
+    };
+
     let mut wallet = node.wallet.write().await;
-    match wallet.receive_funds(from_address.to_string(), amount, currency, 
-                                   transaction_id.to_string(), signature_bytes) {
+    match wallet.receive_funds(from_address.to_string(), amount, currency,
+                                   transaction_id.to_string(), fee, signature_bytes, &public_key_bytes) {
         Ok(_) => axum::Json(serde_json::json!({
             "success": true,
             "message": "Funds received successfully"
@@ -431,6 +553,142 @@ async fn restore_wallet(
     }
 }
 
+async fn backup_wallet_encrypted(
+    State(state): State<ApiState>,
+    axum::Json(request): axum::Json<serde_json::Value>,
+) -> impl IntoResponse {
+    let node = &state.node;
+    let password = request["password"].as_str().unwrap_or("");
+    let wallet = node.wallet.read().await;
+    match wallet.backup_wallet_encrypted(password) {
+        Ok(backup_data) => axum::Json(serde_json::json!({
+            "success": true,
+            "backup_data": backup_data
+        })),
+        Err(e) => {
+            error!("Failed to create encrypted wallet backup: {}", e);
+            axum::Json(serde_json::json!({
+                "success": false,
+                "message": format!("Failed to create encrypted wallet backup: {}", e)
+            }))
+        }
+    }
+}
+
+async fn restore_wallet_encrypted(
+    State(state): State<ApiState>,
+    axum::Json(request): axum::Json<serde_json::Value>,
+) -> impl IntoResponse {
+    let backup_data = request["backup_data"].as_str().unwrap_or("");
+    let password = request["password"].as_str().unwrap_or("");
+
+    match crate::wallet::Wallet::restore_wallet_encrypted(backup_data, password) {
+        Ok(_wallet) => {
+            // In a real implementation, you'd replace the node's wallet
+            axum::Json(serde_json::json!({
+                "success": true,
+                "message": "Wallet restored successfully"
+            }))
+        }
+        Err(e) => {
+            error!("Failed to restore encrypted wallet backup: {}", e);
+            axum::Json(serde_json::json!({
+                "success": false,
+                "message": format!("Failed to restore encrypted wallet backup: {}", e)
+            }))
+        }
+    }
+}
+
+pub(crate) fn parse_currency(symbol: &str) -> crate::wallet::Currency {
+    match symbol {
+        "BTC" => crate::wallet::Currency::BTC,
+        "ETH" => crate::wallet::Currency::ETH,
+        "USDC" => crate::wallet::Currency::USDC,
+        "LTC" => crate::wallet::Currency::LTC,
+        "XMR" => crate::wallet::Currency::XMR,
+        "DOGE" => crate::wallet::Currency::DOGE,
+        _ => crate::wallet::Currency::USDC,
+    }
+}
+
+async fn swap_offer(
+    State(state): State<ApiState>,
+    axum::Json(request): axum::Json<SwapOfferRequest>,
+) -> impl IntoResponse {
+    let node = &state.node;
+
+    let result = node
+        .swap_manager
+        .create_offer(
+            node.did_manager.did.id.clone(),
+            request.counterparty_did,
+            parse_currency(&request.from_currency),
+            request.from_amount,
+            parse_currency(&request.to_currency),
+            request.to_amount,
+        )
+        .await;
+
+    match result {
+        Ok(swap_id) => axum::Json(SwapOfferResponse {
+            swap_id,
+            success: true,
+            message: "Swap offer created successfully".to_string(),
+        }),
+        Err(e) => {
+            error!("Failed to create swap offer: {}", e);
+            axum::Json(SwapOfferResponse {
+                swap_id: "".to_string(),
+                success: false,
+                message: format!("Failed to create swap offer: {}", e),
+            })
+        }
+    }
+}
+
+async fn swap_accept(
+    State(state): State<ApiState>,
+    axum::Json(request): axum::Json<SwapAcceptRequest>,
+) -> impl IntoResponse {
+    let node = &state.node;
+
+    match node
+        .swap_manager
+        .accept_offer(&request.swap_id, &node.did_manager.did.id)
+        .await
+    {
+        Ok(()) => axum::Json(SwapAcceptResponse {
+            success: true,
+            message: "Swap accepted and mirror leg locked".to_string(),
+        }),
+        Err(e) => {
+            error!("Failed to accept swap: {}", e);
+            axum::Json(SwapAcceptResponse {
+                success: false,
+                message: format!("Failed to accept swap: {}", e),
+            })
+        }
+    }
+}
+
+async fn swap_status(
+    State(state): State<ApiState>,
+    axum::extract::Path(swap_id): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    let node = &state.node;
+    match node.swap_manager.get_swap_status(&swap_id).await {
+        Some(swap) => axum::Json(serde_json::json!({
+            "success": true,
+            "swap": swap
+        })),
+        None => axum::Json(serde_json::json!({
+            "success": false,
+            "message": "Swap not found"
+        })),
+    }
+}
+
 async fn get_wallet_keys(State(state): State<ApiState>) -> impl IntoResponse {
     let node = &state.node;
     let wallet = node.wallet.read().await;
@@ -0,0 +1,300 @@
+//! A single `POST /rpc` entry point speaking standard JSON-RPC 2.0, so
+//! tooling and language bindings can target one stable interface instead
+//! of scraping the individual REST routes. The status, service, task,
+//! escrow, reputation, stats, wallet info/balances/addresses/send/
+//! transactions/backup/keys and swap handlers are reachable under named
+//! methods in `dispatch`; the receive, restore, encrypted backup/restore,
+//! rates and payment-request routes have no RPC method. Batched arrays of
+//! calls are supported per the spec.
+
+use crate::api::ApiState;
+use crate::core::data_structures::*;
+use axum::{extract::State, response::IntoResponse};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    #[allow(dead_code)]
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Value,
+    #[serde(default)]
+    id: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcResponse {
+    jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<JsonRpcError>,
+    id: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+impl JsonRpcResponse {
+    fn ok(id: Value, result: Value) -> Self {
+        JsonRpcResponse { jsonrpc: "2.0", result: Some(result), error: None, id }
+    }
+
+    fn err(id: Value, code: i64, message: impl Into<String>) -> Self {
+        JsonRpcResponse {
+            jsonrpc: "2.0",
+            result: None,
+            error: Some(JsonRpcError { code, message: message.into() }),
+            id,
+        }
+    }
+}
+
+pub async fn rpc_handler(
+    State(state): State<ApiState>,
+    axum::Json(body): axum::Json<Value>,
+) -> impl IntoResponse {
+    match body {
+        Value::Array(calls) => {
+            let mut responses = Vec::with_capacity(calls.len());
+            for call in calls {
+                responses.push(handle_call(&state, call).await);
+            }
+            axum::Json(Value::Array(responses.into_iter().map(|r| serde_json::to_value(r).unwrap()).collect()))
+        }
+        single => {
+            let response = handle_call(&state, single).await;
+            axum::Json(serde_json::to_value(response).unwrap())
+        }
+    }
+}
+
+async fn handle_call(state: &ApiState, call: Value) -> JsonRpcResponse {
+    let request: JsonRpcRequest = match serde_json::from_value(call) {
+        Ok(request) => request,
+        Err(e) => return JsonRpcResponse::err(Value::Null, INVALID_REQUEST, format!("invalid request: {}", e)),
+    };
+
+    let JsonRpcRequest { method, params, id, .. } = request;
+    dispatch(state, &method, params)
+        .await
+        .map(|result| JsonRpcResponse::ok(id.clone(), result))
+        .unwrap_or_else(|e| JsonRpcResponse::err(id, e.code, e.message))
+}
+
+struct DispatchError {
+    code: i64,
+    message: String,
+}
+
+fn invalid_params(e: impl std::fmt::Display) -> DispatchError {
+    DispatchError { code: INVALID_PARAMS, message: format!("invalid params: {}", e) }
+}
+
+fn internal_error(e: impl std::fmt::Display) -> DispatchError {
+    DispatchError { code: INTERNAL_ERROR, message: format!("{}", e) }
+}
+
+fn parse_params<T: serde::de::DeserializeOwned>(params: Value) -> Result<T, DispatchError> {
+    serde_json::from_value(params).map_err(invalid_params)
+}
+
+/// The method dispatch table: every entry mirrors the behavior of its
+/// REST counterpart in `api::mod`, just addressed by name instead of path.
+async fn dispatch(state: &ApiState, method: &str, params: Value) -> Result<Value, DispatchError> {
+    let node = &state.node;
+
+    match method {
+        "getStatus" => {
+            let reputation = node.get_reputation(&node.did_manager.did.id).await;
+            let peers = node.network.connected_peers().await;
+            let status = NodeStatus {
+                node_id: node.node_id.0.clone(),
+                did: node.did_manager.did.id.clone(),
+                is_online: true,
+                uptime_seconds: 0,
+                services_count: 0,
+                reputation_score: reputation,
+                peers_count: peers.len(),
+            };
+            Ok(serde_json::to_value(status).unwrap())
+        }
+        "registerService" => {
+            let req: RegisterServiceRequest = parse_params(params)?;
+            node.register_service(req.name, req.description, req.price)
+                .await
+                .map(|id| serde_json::json!({ "service_id": id.0 }))
+                .map_err(internal_error)
+        }
+        "searchServices" => {
+            let req: FindServicesRequest = parse_params(params)?;
+            let services = node.find_services(&req.query).await;
+            Ok(serde_json::json!({ "services": services }))
+        }
+        "submitTask" => {
+            let req: SubmitTaskRequest = parse_params(params)?;
+            let requirements = TaskRequirements {
+                cpu_cores: req.cpu_cores,
+                memory_mb: req.memory_mb,
+                timeout_seconds: req.timeout_seconds,
+            };
+            node.submit_task(ServiceId(req.service_id), req.payload.into_bytes(), requirements)
+                .await
+                .map(|id| serde_json::json!({ "task_id": id.0 }))
+                .map_err(internal_error)
+        }
+        "createEscrow" => {
+            let req: CreateEscrowRequest = parse_params(params)?;
+            let service_id = ServiceId(req.service_id);
+            let result = match (req.usd_amount, req.currency) {
+                (Some(usd_amount), Some(currency)) => {
+                    let usd_amount = usd_amount.parse::<rust_decimal::Decimal>().map_err(invalid_params)?;
+                    node.create_escrow_for_service_usd(&service_id, req.seller_did, usd_amount, crate::api::parse_currency(&currency))
+                        .await
+                }
+                _ => node.create_escrow_for_service(&service_id, req.seller_did, req.amount).await,
+            };
+            result
+                .map(|escrow_id| serde_json::json!({ "escrow_id": escrow_id }))
+                .map_err(internal_error)
+        }
+        "getReputation" => {
+            let did = params["did"].as_str().ok_or_else(|| invalid_params("missing did"))?;
+            Ok(serde_json::json!({ "did": did, "reputation": node.get_reputation(did).await }))
+        }
+        "getStats" => {
+            let dht_stats = node.dht.get_stats().await;
+            let dht_metrics = node.dht.get_metrics().await;
+            let reputation_stats = node.reputation_system.get_stats().await;
+            let escrow_stats = node.escrow_manager.get_stats().await;
+            let task_stats = node.task_engine.get_stats().await;
+            let network_stats = node.network.get_stats().await;
+            Ok(serde_json::json!({
+                "dht": {
+                    "total_entries": dht_stats.total_entries,
+                    "total_peers": dht_stats.total_peers,
+                    "service_entries": dht_stats.service_entries,
+                    "reputation_entries": dht_stats.reputation_entries,
+                    "escrow_entries": dht_stats.escrow_entries,
+                },
+                "dht_metrics": dht_metrics,
+                "reputation": {
+                    "total_nodes": reputation_stats.total_nodes,
+                    "total_attestations": reputation_stats.total_attestations,
+                    "average_score": reputation_stats.average_score,
+                },
+                "escrow": {
+                    "total_contracts": escrow_stats.total_contracts,
+                    "created": escrow_stats.created,
+                    "funded": escrow_stats.funded,
+                    "in_progress": escrow_stats.in_progress,
+                    "completed": escrow_stats.completed,
+                    "disputed": escrow_stats.disputed,
+                    "refunded": escrow_stats.refunded,
+                    "total_amount": escrow_stats.total_amount,
+                },
+                "tasks": {
+                    "pending_count": task_stats.pending_count,
+                    "processing_count": task_stats.processing_count,
+                    "completed_count": task_stats.completed_count,
+                    "total_tasks": task_stats.total_tasks,
+                },
+                "network": {
+                    "local_peer_id": network_stats.local_peer_id,
+                    "connected_peers": network_stats.connected_peers,
+                    "subscribed_topics": network_stats.subscribed_topics,
+                },
+            }))
+        }
+        "wallet_getInfo" => {
+            let wallet = node.wallet.read().await;
+            wallet.get_wallet_info().map(|info| serde_json::to_value(info).unwrap()).map_err(internal_error)
+        }
+        "wallet_getBalances" => {
+            let wallet = node.wallet.read().await;
+            let mut balances = std::collections::HashMap::new();
+            for (currency, amount) in wallet.get_all_balances() {
+                balances.insert(currency.symbol().to_string(), currency.format_amount(amount));
+            }
+            Ok(serde_json::json!({ "balances": balances, "total_usd": wallet.get_total_balance_usd(node.price_oracle.as_ref()) }))
+        }
+        "wallet_getAddresses" => {
+            let wallet = node.wallet.read().await;
+            let mut addresses = std::collections::HashMap::new();
+            for (currency, address) in wallet.get_all_addresses() {
+                addresses.insert(currency.symbol().to_string(), address);
+            }
+            Ok(serde_json::json!({ "addresses": addresses }))
+        }
+        "wallet_send" => {
+            let req: crate::wallet::SendRequest = parse_params(params)?;
+            let mut wallet = node.wallet.write().await;
+            wallet.send_funds(req).map(|r| serde_json::to_value(r).unwrap()).map_err(internal_error)
+        }
+        "wallet_getTransactions" => {
+            let wallet = node.wallet.read().await;
+            Ok(serde_json::json!({ "transactions": wallet.get_transaction_history() }))
+        }
+        "wallet_getTransaction" => {
+            let transaction_id = params["transaction_id"].as_str().ok_or_else(|| invalid_params("missing transaction_id"))?;
+            let wallet = node.wallet.read().await;
+            wallet
+                .get_transaction_by_id(transaction_id)
+                .map(|t| serde_json::to_value(t).unwrap())
+                .ok_or_else(|| internal_error("transaction not found"))
+        }
+        "wallet_backup" => {
+            let wallet = node.wallet.read().await;
+            wallet.backup_wallet().map(|data| serde_json::json!({ "backup_data": data })).map_err(internal_error)
+        }
+        "wallet_getKeys" => {
+            let wallet = node.wallet.read().await;
+            match (wallet.get_public_key_base64(), wallet.get_private_key_base64()) {
+                (Ok(public_key), Ok(private_key)) => Ok(serde_json::json!({ "public_key": public_key, "private_key": private_key })),
+                (Err(e), _) | (_, Err(e)) => Err(internal_error(e)),
+            }
+        }
+        "swap_offer" => {
+            let req: SwapOfferRequest = parse_params(params)?;
+            node.swap_manager
+                .create_offer(
+                    node.did_manager.did.id.clone(),
+                    req.counterparty_did,
+                    crate::api::parse_currency(&req.from_currency),
+                    req.from_amount,
+                    crate::api::parse_currency(&req.to_currency),
+                    req.to_amount,
+                )
+                .await
+                .map(|swap_id| serde_json::json!({ "swap_id": swap_id }))
+                .map_err(internal_error)
+        }
+        "swap_accept" => {
+            let req: SwapAcceptRequest = parse_params(params)?;
+            node.swap_manager
+                .accept_offer(&req.swap_id, &node.did_manager.did.id)
+                .await
+                .map(|_| serde_json::json!({ "success": true }))
+                .map_err(internal_error)
+        }
+        "swap_status" => {
+            let swap_id = params["swap_id"].as_str().ok_or_else(|| invalid_params("missing swap_id"))?;
+            node.swap_manager
+                .get_swap_status(swap_id)
+                .await
+                .map(|swap| serde_json::to_value(swap).unwrap())
+                .ok_or_else(|| internal_error("swap not found"))
+        }
+        other => Err(DispatchError { code: METHOD_NOT_FOUND, message: format!("method not found: {}", other) }),
+    }
+}
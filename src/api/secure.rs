@@ -0,0 +1,245 @@
+//! An encrypted channel for the wallet's most sensitive owner operations
+//! (private key export, seed backups, sends), so they can be carried
+//! encrypted over the otherwise CORS-open API. The cleartext
+//! `/api/wallet/keys`, `/api/wallet/backup` and `/api/wallet/send` routes,
+//! and the matching `/rpc` methods, remain registered alongside it.
+//!
+//! `POST /api/owner/init_secure` performs an X25519 ECDH handshake and
+//! derives an AES-256-GCM session key via HKDF-SHA256 over the shared
+//! secret. Every following request goes through the single
+//! `POST /api/owner/encrypted` route as a base64 AES-256-GCM ciphertext
+//! wrapping a JSON-RPC 2.0 request; the response is wrapped the same way.
+
+use crate::api::ApiState;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use axum::{extract::State, response::IntoResponse};
+use base64::{engine::general_purpose, Engine as _};
+use hkdf::Hkdf;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::error;
+use x25519_dalek::{EphemeralSecret, PublicKey};
+
+const SESSION_TTL_SECS: u64 = 300; // 5 minutes
+
+pub struct SecureSession {
+    key: [u8; 32],
+    created_at: u64,
+}
+
+pub type SecureSessions = Arc<RwLock<HashMap<String, SecureSession>>>;
+
+#[derive(Debug, Deserialize)]
+pub struct InitSecureRequest {
+    pub client_public_key: String, // base64 X25519 public key
+}
+
+#[derive(Debug, Serialize)]
+pub struct InitSecureResponse {
+    pub success: bool,
+    pub session_id: String,
+    pub server_public_key: String, // base64 X25519 public key
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EncryptedRequest {
+    pub session_id: String,
+    pub nonce: String,      // base64, 12 bytes
+    pub ciphertext: String, // base64
+}
+
+#[derive(Debug, Serialize)]
+pub struct EncryptedResponse {
+    pub success: bool,
+    pub nonce: Option<String>,
+    pub ciphertext: Option<String>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    #[allow(dead_code)]
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: serde_json::Value,
+    id: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcResponse {
+    jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<serde_json::Value>,
+    id: serde_json::Value,
+}
+
+pub async fn init_secure(
+    State(state): State<ApiState>,
+    axum::Json(request): axum::Json<InitSecureRequest>,
+) -> impl IntoResponse {
+    match handle_init_secure(&state, request).await {
+        Ok(response) => axum::Json(response),
+        Err(e) => {
+            error!("Failed to initialize secure session: {}", e);
+            axum::Json(InitSecureResponse {
+                success: false,
+                session_id: String::new(),
+                server_public_key: String::new(),
+            })
+        }
+    }
+}
+
+async fn handle_init_secure(
+    state: &ApiState,
+    request: InitSecureRequest,
+) -> anyhow::Result<InitSecureResponse> {
+    let client_key_bytes: [u8; 32] = general_purpose::STANDARD
+        .decode(&request.client_public_key)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("client_public_key must be 32 bytes"))?;
+    let client_public = PublicKey::from(client_key_bytes);
+
+    let server_secret = EphemeralSecret::random_from_rng(rand::rngs::OsRng);
+    let server_public = PublicKey::from(&server_secret);
+    let shared_secret = server_secret.diffie_hellman(&client_public);
+
+    let mut session_key = [0u8; 32];
+    Hkdf::<Sha256>::new(None, shared_secret.as_bytes())
+        .expand(b"duxnet-owner-channel", &mut session_key)
+        .map_err(|_| anyhow::anyhow!("HKDF expand failed"))?;
+
+    let session_id = uuid::Uuid::new_v4().to_string();
+    state.secure_sessions.write().await.insert(
+        session_id.clone(),
+        SecureSession {
+            key: session_key,
+            created_at: crate::core::data_structures::get_current_timestamp(),
+        },
+    );
+
+    Ok(InitSecureResponse {
+        success: true,
+        session_id,
+        server_public_key: general_purpose::STANDARD.encode(server_public.as_bytes()),
+    })
+}
+
+pub async fn encrypted(
+    State(state): State<ApiState>,
+    axum::Json(request): axum::Json<EncryptedRequest>,
+) -> impl IntoResponse {
+    match handle_encrypted(&state, request).await {
+        Ok(response) => axum::Json(response),
+        Err(e) => {
+            error!("Failed to process encrypted owner request: {}", e);
+            axum::Json(EncryptedResponse {
+                success: false,
+                nonce: None,
+                ciphertext: None,
+                message: Some(format!("{}", e)),
+            })
+        }
+    }
+}
+
+async fn handle_encrypted(state: &ApiState, request: EncryptedRequest) -> anyhow::Result<EncryptedResponse> {
+    let session_key = {
+        let mut sessions = state.secure_sessions.write().await;
+        let session = sessions
+            .get(&request.session_id)
+            .ok_or_else(|| anyhow::anyhow!("unknown or expired secure session"))?;
+        let now = crate::core::data_structures::get_current_timestamp();
+        if now >= session.created_at + SESSION_TTL_SECS {
+            sessions.remove(&request.session_id);
+            return Err(anyhow::anyhow!("secure session expired"));
+        }
+        session.key
+    };
+
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&session_key));
+    let nonce_bytes = general_purpose::STANDARD.decode(&request.nonce)?;
+    let ciphertext = general_purpose::STANDARD.decode(&request.ciphertext)?;
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce_bytes), ciphertext.as_ref())
+        .map_err(|_| anyhow::anyhow!("failed to decrypt owner request"))?;
+
+    let rpc_request: JsonRpcRequest = serde_json::from_slice(&plaintext)?;
+    let rpc_response = dispatch(state, rpc_request).await;
+
+    let mut response_nonce = [0u8; 12];
+    rand::rngs::OsRng.fill_bytes(&mut response_nonce);
+    let response_plaintext = serde_json::to_vec(&rpc_response)?;
+    let response_ciphertext = cipher
+        .encrypt(Nonce::from_slice(&response_nonce), response_plaintext.as_ref())
+        .map_err(|_| anyhow::anyhow!("failed to encrypt owner response"))?;
+
+    Ok(EncryptedResponse {
+        success: true,
+        nonce: Some(general_purpose::STANDARD.encode(response_nonce)),
+        ciphertext: Some(general_purpose::STANDARD.encode(response_ciphertext)),
+        message: None,
+    })
+}
+
+/// Dispatches a decrypted owner request to the same wallet operations the
+/// cleartext routes expose, with the same semantics.
+async fn dispatch(state: &ApiState, request: JsonRpcRequest) -> JsonRpcResponse {
+    let node = &state.node;
+
+    let result = match request.method.as_str() {
+        "wallet.get_keys" => {
+            let wallet = node.wallet.read().await;
+            match (wallet.get_public_key_base64(), wallet.get_private_key_base64()) {
+                (Ok(public_key), Ok(private_key)) => Ok(serde_json::json!({
+                    "public_key": public_key,
+                    "private_key": private_key,
+                })),
+                (Err(e), _) | (_, Err(e)) => Err(e.to_string()),
+            }
+        }
+        "wallet.backup" => {
+            let wallet = node.wallet.read().await;
+            wallet
+                .backup_wallet()
+                .map(|backup_data| serde_json::json!({ "backup_data": backup_data }))
+                .map_err(|e| e.to_string())
+        }
+        "wallet.send_funds" => {
+            match serde_json::from_value::<crate::wallet::SendRequest>(request.params) {
+                Ok(send_request) => {
+                    let mut wallet = node.wallet.write().await;
+                    wallet
+                        .send_funds(send_request)
+                        .map(|response| serde_json::to_value(response).unwrap())
+                        .map_err(|e| e.to_string())
+                }
+                Err(e) => Err(format!("invalid params: {}", e)),
+            }
+        }
+        other => Err(format!("unknown method: {}", other)),
+    };
+
+    match result {
+        Ok(value) => JsonRpcResponse {
+            jsonrpc: "2.0",
+            result: Some(value),
+            error: None,
+            id: request.id,
+        },
+        Err(message) => JsonRpcResponse {
+            jsonrpc: "2.0",
+            result: None,
+            error: Some(serde_json::json!({ "code": -32000, "message": message })),
+            id: request.id,
+        },
+    }
+}
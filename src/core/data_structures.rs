@@ -32,6 +32,10 @@ pub struct ServiceMetadata {
     pub price: u64,
     pub reputation_score: f64,
     pub last_updated: u64,
+    /// Provider's signature over `identity::service_message(self)`,
+    /// checked by `DHT::store` before a `service:` entry is admitted so a
+    /// node can't have its listing forged or tampered with in transit.
+    pub signature: Vec<u8>,
 }
 
 // Reputation system
@@ -53,10 +57,49 @@ pub struct EscrowContract {
     pub seller_did: String,
     pub arbiters: Vec<String>,
     pub amount: u64,
+    /// The service this contract is paying for, so a party or arbiter can
+    /// look up what's actually being delivered. `None` for escrows not
+    /// tied to a marketplace listing, e.g. an atomic swap leg.
+    pub service_id: Option<ServiceId>,
     pub state: EscrowState,
     pub multisig_address: String,
-    pub signatures: HashMap<String, Vec<u8>>,
+    /// Compressed FROST group verifying key (ristretto25519), produced
+    /// once during `create_escrow`'s DKG.
+    pub group_public_key: Vec<u8>,
+    /// DID -> FROST participant index (x-coordinate, 1-based), needed to
+    /// recompute Lagrange coefficients for whichever subset signs.
+    pub participant_indices: HashMap<String, u16>,
+    /// The aggregated `(R, z)` Schnorr signature once enough participants
+    /// have signed off on the current state transition, encoded as 32
+    /// bytes of compressed R followed by 32 bytes of z.
+    pub settlement_signature: Option<Vec<u8>>,
+    /// Cashu proofs locked to a P2PK spending condition for this escrow,
+    /// populated when settlement runs over ecash rather than a chain.
+    pub locked_proofs: Vec<CashuProof>,
     pub created_at: u64,
+    /// Timestamp after which a contract still stuck in `Created`,
+    /// `Funded`, or `InProgress` is swept into an automatic refund — see
+    /// `EscrowManager::check_funding_timeouts`.
+    pub funding_deadline: u64,
+}
+
+// Cashu ecash escrow settlement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CashuProof {
+    pub amount: u64,
+    pub id: String,
+    pub secret: String,
+    pub c: Vec<u8>,
+}
+
+/// NUT-11-style P2PK spending condition: normally only `seller_pubkey` can
+/// sign, but `arbiter_pubkey` may also sign once `refund_locktime` passes,
+/// and after that the buyer can reclaim unconditionally.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct P2PKLock {
+    pub seller_pubkey: Vec<u8>,
+    pub arbiter_pubkey: Vec<u8>,
+    pub refund_locktime: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -111,8 +154,13 @@ pub enum NetworkMessage {
     
     // Escrow management
     EscrowCreation(EscrowContract),
-    EscrowSignature(String, String, Vec<u8>), // escrow_id, signer_did, signature
+    EscrowSignature(String, String, EscrowState, Vec<u8>), // escrow_id, signer_did, state, signature
     EscrowStateUpdate(String, EscrowState),
+    DisputeOpened(String, String), // escrow_id, raised_by
+
+    // Cashu ecash escrow
+    EcashLockedProofs(String, Vec<CashuProof>), // escrow_id, proofs locked by the buyer
+    EcashUnlockWitness(String, Vec<u8>), // escrow_id, signature releasing the locked proofs
     
     // Reputation
     ReputationAttestation(ReputationAttestation),
@@ -172,6 +220,13 @@ pub struct CreateEscrowRequest {
     pub service_id: String,
     pub seller_did: String,
     pub amount: u64,
+    /// When set (together with `currency`), denominates the locked amount
+    /// in USD instead of `amount`, resolved to the native unit via the
+    /// current rate at lock time.
+    #[serde(default)]
+    pub usd_amount: Option<String>,
+    #[serde(default)]
+    pub currency: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -181,6 +236,33 @@ pub struct CreateEscrowResponse {
     pub message: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapOfferRequest {
+    pub counterparty_did: String,
+    pub from_currency: String,
+    pub from_amount: u64,
+    pub to_currency: String,
+    pub to_amount: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapOfferResponse {
+    pub swap_id: String,
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapAcceptRequest {
+    pub swap_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapAcceptResponse {
+    pub success: bool,
+    pub message: String,
+}
+
 // Node status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NodeStatus {
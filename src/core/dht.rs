@@ -1,11 +1,83 @@
 use crate::core::data_structures::*;
+use crate::core::dht_store::DhtStore;
+use crate::core::metrics::{Metrics, MetricsSnapshot};
 use anyhow::Result;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::RwLock;
 use tracing::{debug, info};
 
+/// Number of bits in a Kademlia id — one bucket per possible shared-prefix
+/// length with our own `node_id`.
+const ID_BITS: usize = 256;
+/// Peers queried in parallel per round of `iterative_lookup`.
+const ALPHA: usize = 3;
+
+type KademliaId = [u8; 32];
+
+/// Hashes `input` into a 256-bit Kademlia id with Keccak-256, the same
+/// hash Ethereum's node discovery protocol uses to place both node ids
+/// and content keys in the same id space.
+fn hash_id(input: &str) -> KademliaId {
+    let mut hasher = Keccak256::new();
+    hasher.update(input.as_bytes());
+    let digest = hasher.finalize();
+    let mut id = [0u8; 32];
+    id.copy_from_slice(&digest);
+    id
+}
+
+/// XOR distance between two ids, interpreted as a big-endian big integer
+/// (so ordinary byte-array `Ord` comparison is distance comparison).
+fn xor_distance(a: &KademliaId, b: &KademliaId) -> KademliaId {
+    let mut out = [0u8; 32];
+    for i in 0..32 {
+        out[i] = a[i] ^ b[i];
+    }
+    out
+}
+
+/// Number of leading bits `a` and `b` share before the first differing
+/// bit — the index of the k-bucket a peer with id `a` belongs in,
+/// relative to a local node with id `b`.
+fn shared_prefix_len(a: &KademliaId, b: &KademliaId) -> usize {
+    for (i, (x, y)) in a.iter().zip(b.iter()).enumerate() {
+        let diff = x ^ y;
+        if diff != 0 {
+            return i * 8 + diff.leading_zeros() as usize;
+        }
+    }
+    ID_BITS
+}
+
 #[derive(Debug, Clone)]
+struct PeerContact {
+    peer_id: String,
+    id: KademliaId,
+    last_seen: u64,
+}
+
+/// A single k-bucket: up to `k_bucket_size` contacts, ordered
+/// least-recently-seen (front) to most-recently-seen (back), per the
+/// classic Kademlia eviction policy.
+#[derive(Debug, Clone, Default)]
+struct KBucket {
+    contacts: VecDeque<PeerContact>,
+}
+
+/// Whether this `DHT` keeps a full copy of the network's data or is a
+/// light client that leans on full nodes for it. See `DHT::light`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeMode {
+    Full,
+    Light,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DHTEntry {
     pub key: String,
     pub value: Vec<u8>,
@@ -13,59 +85,346 @@ pub struct DHTEntry {
     pub ttl: u64,
 }
 
+/// A Kademlia-style DHT: a 256-bit id space (node and content keys share
+/// it, both hashed with Keccak-256), a routing table of 256 k-buckets
+/// bucketed by XOR-distance prefix length, and an iterative closest-node
+/// lookup over that table.
+///
+/// This process is the only concrete storage surface it has — there's no
+/// peer-query transport wired in yet (that lives in
+/// `crate::network::Network`'s separate libp2p Kademlia behaviour), so
+/// `iterative_lookup`'s "ask a contact for its closest peers" step falls
+/// back to consulting our own routing table rather than a real remote
+/// call, and `store`/`get` still read and write local `entries`. The
+/// routing table, distance metric, and lookup/eviction algorithms are
+/// real; wiring a remote query into `iterative_lookup` is the natural
+/// next step once the DHT is connected to the network layer.
 pub struct DHT {
     pub node_id: NodeId,
+    id: KademliaId,
     pub entries: Arc<RwLock<HashMap<String, DHTEntry>>>,
-    pub peers: Arc<RwLock<Vec<String>>>,
+    buckets: Arc<RwLock<Vec<KBucket>>>,
     pub k_bucket_size: usize,
+    /// Pluggable on-disk backing store. `None` means purely in-memory,
+    /// the original behavior; see `with_persistence` to enable it.
+    persistence: Option<Arc<DhtStore>>,
+    mode: NodeMode,
+    pub metrics: Metrics,
 }
 
 impl DHT {
     pub fn new(node_id: NodeId) -> Self {
+        let id = hash_id(&node_id.0);
+        DHT {
+            node_id,
+            id,
+            entries: Arc::new(RwLock::new(HashMap::new())),
+            buckets: Arc::new(RwLock::new(vec![KBucket::default(); ID_BITS])),
+            k_bucket_size: 20,
+            persistence: None,
+            mode: NodeMode::Full,
+            metrics: Metrics::new(),
+        }
+    }
+
+    /// A light client: it never keeps a full copy of the network's data
+    /// and never persists other peers' entries to disk, so it's always
+    /// constructed without a backing store. `get`, `find_services`,
+    /// `get_reputation_attestations`, and `get_escrow_contract` instead
+    /// resolve by running the iterative closest-node lookup against the
+    /// nearest full nodes and treating `entries` as a short-lived,
+    /// TTL-bounded cache of whatever that lookup has turned up — and,
+    /// because that cache isn't trusted the way a full node's own store
+    /// is, reputation attestations and service listings are re-verified
+    /// against their signer's resolved key before being returned.
+    pub fn light(node_id: NodeId) -> Self {
+        let id = hash_id(&node_id.0);
         DHT {
             node_id,
+            id,
             entries: Arc::new(RwLock::new(HashMap::new())),
-            peers: Arc::new(RwLock::new(Vec::new())),
+            buckets: Arc::new(RwLock::new(vec![KBucket::default(); ID_BITS])),
+            k_bucket_size: 20,
+            persistence: None,
+            mode: NodeMode::Light,
+            metrics: Metrics::new(),
+        }
+    }
+
+    /// Like `new`, but backs `entries` with a journaled on-disk store
+    /// under `dir`: existing data is loaded immediately (running any
+    /// pending schema migration and replaying the write-ahead journal on
+    /// top of the last snapshot first), and every subsequent `store`/
+    /// `remove` is appended to the journal before it's applied in memory.
+    pub fn with_persistence(node_id: NodeId, dir: impl AsRef<Path>) -> Result<Self> {
+        let store = DhtStore::new(dir);
+        let loaded = store.load()?;
+        let id = hash_id(&node_id.0);
+        Ok(DHT {
+            node_id,
+            id,
+            entries: Arc::new(RwLock::new(loaded)),
+            buckets: Arc::new(RwLock::new(vec![KBucket::default(); ID_BITS])),
             k_bucket_size: 20,
+            persistence: Some(Arc::new(store)),
+            mode: NodeMode::Full,
+            metrics: Metrics::new(),
+        })
+    }
+
+    /// Folds the write-ahead journal into the on-disk snapshot and
+    /// truncates it. A no-op when persistence isn't enabled. Callers
+    /// should invoke this periodically so the journal doesn't grow
+    /// without bound.
+    pub async fn compact(&self) -> Result<()> {
+        if let Some(store) = &self.persistence {
+            let entries = self.entries.read().await;
+            store.compact(&entries)?;
+        }
+        Ok(())
+    }
+
+    fn bucket_index(&self, peer_id: &KademliaId) -> usize {
+        shared_prefix_len(peer_id, &self.id).min(ID_BITS - 1)
+    }
+
+    /// Without a live transport wired to the DHT, we can't actually send
+    /// a ping; we conservatively treat a peer already in our table as
+    /// still alive, matching Kademlia's bias toward long-lived, proven
+    /// nodes over new, unverified ones.
+    async fn ping(&self, _peer_id: &str) -> bool {
+        true
+    }
+
+    /// Inserts or refreshes `peer_id` in the routing table. A peer
+    /// already present moves to the most-recently-seen end. A new peer
+    /// arriving at a full bucket only evicts the least-recently-seen
+    /// contact if that contact fails a ping; otherwise the new peer is
+    /// dropped and the old one is refreshed instead. Self-ids are ignored.
+    pub async fn add_peer(&self, peer_id: String) -> Result<()> {
+        let candidate_id = hash_id(&peer_id);
+        if candidate_id == self.id {
+            return Ok(());
+        }
+
+        let index = self.bucket_index(&candidate_id);
+        let now = get_current_timestamp();
+        let mut buckets = self.buckets.write().await;
+        let bucket = &mut buckets[index];
+
+        if let Some(position) = bucket.contacts.iter().position(|c| c.peer_id == peer_id) {
+            let mut contact = bucket.contacts.remove(position).unwrap();
+            contact.last_seen = now;
+            bucket.contacts.push_back(contact);
+            return Ok(());
+        }
+
+        if bucket.contacts.len() < self.k_bucket_size {
+            bucket.contacts.push_back(PeerContact { peer_id: peer_id.clone(), id: candidate_id, last_seen: now });
+            debug!("Added peer {} to k-bucket {}", peer_id, index);
+            return Ok(());
+        }
+
+        let least_recently_seen = bucket.contacts.front().map(|c| c.peer_id.clone());
+        drop(buckets);
+        let stale = match &least_recently_seen {
+            Some(oldest) => !self.ping(oldest).await,
+            None => false,
+        };
+
+        let mut buckets = self.buckets.write().await;
+        let bucket = &mut buckets[index];
+        if stale {
+            bucket.contacts.pop_front();
+            bucket.contacts.push_back(PeerContact { peer_id: peer_id.clone(), id: candidate_id, last_seen: now });
+            debug!("Evicted stale peer from k-bucket {} for {}", index, peer_id);
+        } else if let Some(mut oldest) = bucket.contacts.pop_front() {
+            oldest.last_seen = now;
+            bucket.contacts.push_back(oldest);
+            debug!("k-bucket {} full; kept responsive peer over {}", index, peer_id);
+        }
+        Ok(())
+    }
+
+    pub async fn remove_peer(&self, peer_id: &str) -> Result<()> {
+        let mut buckets = self.buckets.write().await;
+        for bucket in buckets.iter_mut() {
+            bucket.contacts.retain(|c| c.peer_id != peer_id);
+        }
+        debug!("Removed peer: {}", peer_id);
+        Ok(())
+    }
+
+    pub async fn get_peers(&self) -> Vec<String> {
+        let buckets = self.buckets.read().await;
+        buckets.iter().flat_map(|b| b.contacts.iter().map(|c| c.peer_id.clone())).collect()
+    }
+
+    /// The `count` routing-table peers closest to `target`, nearest
+    /// first, excluding `exclude` and our own id.
+    async fn closest_known_peers(&self, target: &KademliaId, count: usize, exclude: &HashSet<String>) -> Vec<PeerContact> {
+        let buckets = self.buckets.read().await;
+        let mut candidates: Vec<PeerContact> = buckets
+            .iter()
+            .flat_map(|b| b.contacts.iter().cloned())
+            .filter(|c| !exclude.contains(&c.peer_id))
+            .collect();
+        candidates.sort_by_key(|c| xor_distance(&c.id, target));
+        candidates.truncate(count);
+        candidates
+    }
+
+    /// Iteratively narrows a shortlist of the `k_bucket_size` peers
+    /// closest to `target_key` down to the true closest set: each round
+    /// asks up to `ALPHA` not-yet-queried contacts from the shortlist for
+    /// their own closest contacts, merges the responses in, and stops
+    /// once a round fails to surface anything closer than what's already
+    /// known. Self-excluded, since a node never needs to route through
+    /// itself. See the type-level doc comment for why "asking" a contact
+    /// currently just re-consults our own table.
+    pub async fn iterative_lookup(&self, target_key: &str) -> Vec<String> {
+        let target = hash_id(target_key);
+        let mut shortlist = self.closest_known_peers(&target, self.k_bucket_size, &HashSet::new()).await;
+        let mut queried: HashSet<String> = HashSet::new();
+        let mut hops: u64 = 0;
+
+        loop {
+            let to_query: Vec<PeerContact> =
+                shortlist.iter().filter(|c| !queried.contains(&c.peer_id)).take(ALPHA).cloned().collect();
+            if to_query.is_empty() {
+                break;
+            }
+            hops += 1;
+            for contact in &to_query {
+                queried.insert(contact.peer_id.clone());
+            }
+
+            let mut candidates = shortlist.clone();
+            for _ in &to_query {
+                candidates.extend(self.closest_known_peers(&target, self.k_bucket_size, &HashSet::new()).await);
+            }
+            candidates.sort_by_key(|c| xor_distance(&c.id, &target));
+            candidates.dedup_by(|a, b| a.peer_id == b.peer_id);
+            candidates.truncate(self.k_bucket_size);
+
+            let closer_than_before = match (shortlist.first(), candidates.first()) {
+                (Some(before), Some(best)) => xor_distance(&best.id, &target) < xor_distance(&before.id, &target),
+                (None, Some(_)) => true,
+                _ => false,
+            };
+            shortlist = candidates;
+            if !closer_than_before {
+                break;
+            }
         }
+
+        self.metrics.record_lookup_hops(hops).await;
+        shortlist.into_iter().map(|c| c.peer_id).collect()
     }
 
     pub async fn store(&self, key: String, value: Vec<u8>, ttl: u64) -> Result<()> {
+        let start = Instant::now();
+        if key.starts_with("service:") {
+            self.verify_service_announcement(&value).await?;
+        }
+
+        let replicas = self.iterative_lookup(&key).await;
         let entry = DHTEntry {
             key: key.clone(),
             value,
             ttl,
             timestamp: get_current_timestamp(),
         };
-        
+
+        if let Some(persistence) = &self.persistence {
+            persistence.append_put(&key, &entry)?;
+        }
+
         let mut store = self.entries.write().await;
         store.insert(key.clone(), entry);
-        debug!("Stored DHT entry: {}", key);
+        drop(store);
+        self.metrics.record_store(&key, start.elapsed()).await;
+        debug!("Stored DHT entry: {} (closest peers: {})", key, replicas.len());
         Ok(())
     }
 
     pub async fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let start = Instant::now();
+        if self.mode == NodeMode::Light {
+            // Stand-in for a real remote GET RPC: the closest-node
+            // discovery step runs for real, but until a peer-query
+            // transport exists the value itself can only come from
+            // whatever a prior `store()` (driven by an incoming network
+            // message) has already cached locally.
+            self.iterative_lookup(key).await;
+        }
+
         let entries = self.entries.read().await;
         let now = get_current_timestamp();
-        
-        if let Some(entry) = entries.get(key) {
+
+        let result = if let Some(entry) = entries.get(key) {
             if now < entry.timestamp + entry.ttl {
                 debug!("Retrieved DHT entry: {}", key);
-                return Some(entry.value.clone());
+                Some(entry.value.clone())
             } else {
                 debug!("DHT entry expired: {}", key);
+                None
             }
-        }
-        None
+        } else {
+            None
+        };
+        drop(entries);
+
+        self.metrics.record_get(key, start.elapsed(), result.is_some()).await;
+        result
     }
 
     pub async fn remove(&self, key: &str) -> Result<()> {
+        if let Some(persistence) = &self.persistence {
+            persistence.append_remove(key)?;
+        }
+
         let mut entries = self.entries.write().await;
         entries.remove(key);
         debug!("Removed DHT entry: {}", key);
         Ok(())
     }
 
+    /// Rejects a `service:` entry whose signature doesn't check out
+    /// against its `provider_did`'s resolved key, so `store` can't be
+    /// used to forge or tamper with another provider's listing.
+    async fn verify_service_announcement(&self, value: &[u8]) -> Result<()> {
+        let service: ServiceMetadata = serde_json::from_slice(value)
+            .map_err(|e| anyhow::anyhow!("malformed service announcement: {}", e))?;
+        if !self.service_signature_valid(&service).await {
+            return Err(anyhow::anyhow!("service announcement for {} has an invalid signature", service.id.0));
+        }
+        Ok(())
+    }
+
+    /// Whether `service.signature` actually matches its `provider_did`'s
+    /// resolved key — shared by `store`'s admission check and a light
+    /// client's re-verification of cached listings in `find_services`.
+    async fn service_signature_valid(&self, service: &ServiceMetadata) -> bool {
+        let Some(provider) = self.resolve_did(&service.provider_did).await else {
+            return false;
+        };
+        let message = crate::core::identity::service_message(service);
+        crate::core::identity::verify_with_public_key(&provider.public_key, message.as_bytes(), &service.signature)
+    }
+
+    /// Whether `attestation.signature` actually matches its
+    /// `attester_did`'s resolved key — used by a light client to
+    /// re-verify cached attestations in `get_reputation_attestations`,
+    /// mirroring `ReputationSystem::add_attestation`'s check.
+    async fn attestation_signature_valid(&self, attestation: &ReputationAttestation) -> bool {
+        let Some(attester) = self.resolve_did(&attestation.attester_did).await else {
+            return false;
+        };
+        let message = crate::core::identity::attestation_message(attestation);
+        crate::core::identity::verify_with_public_key(&attester.public_key, message.as_bytes(), &attestation.signature)
+    }
+
     pub async fn announce_service(&self, service: &ServiceMetadata) -> Result<()> {
         let key = format!("service:{}", service.id.0);
         let value = serde_json::to_vec(service)?;
@@ -73,24 +432,52 @@ impl DHT {
     }
 
     pub async fn find_services(&self, query: &str) -> Vec<ServiceMetadata> {
+        if self.mode == NodeMode::Light {
+            self.iterative_lookup(query).await;
+        }
+
+        let now = get_current_timestamp();
         let entries = self.entries.read().await;
         let mut services = Vec::new();
-        
+
         for (key, entry) in entries.iter() {
-            if key.starts_with("service:") {
+            if key.starts_with("service:") && (self.mode == NodeMode::Full || now < entry.timestamp + entry.ttl) {
                 if let Ok(service) = serde_json::from_slice::<ServiceMetadata>(&entry.value) {
-                    if service.name.to_lowercase().contains(&query.to_lowercase()) || 
+                    if service.name.to_lowercase().contains(&query.to_lowercase()) ||
                        service.description.to_lowercase().contains(&query.to_lowercase()) {
                         services.push(service);
                     }
                 }
             }
         }
-        
+        drop(entries);
+
+        if self.mode == NodeMode::Light {
+            let mut verified = Vec::with_capacity(services.len());
+            for service in services {
+                if self.service_signature_valid(&service).await {
+                    verified.push(service);
+                }
+            }
+            services = verified;
+        }
+
         debug!("Found {} services for query: {}", services.len(), query);
         services
     }
 
+    pub async fn announce_did(&self, did: &DID) -> Result<()> {
+        let key = format!("did:{}", did.id);
+        let value = serde_json::to_vec(did)?;
+        self.store(key, value, 86400).await // 24 hour TTL, re-announced like services
+    }
+
+    pub async fn resolve_did(&self, did_id: &str) -> Option<DID> {
+        let key = format!("did:{}", did_id);
+        let value = self.get(&key).await?;
+        serde_json::from_slice(&value).ok()
+    }
+
     pub async fn store_reputation_attestation(&self, attestation: &ReputationAttestation) -> Result<()> {
         let key = format!("reputation:{}:{}", attestation.target_did, attestation.timestamp);
         let value = serde_json::to_vec(attestation)?;
@@ -98,17 +485,34 @@ impl DHT {
     }
 
     pub async fn get_reputation_attestations(&self, target_did: &str) -> Vec<ReputationAttestation> {
+        if self.mode == NodeMode::Light {
+            self.iterative_lookup(&format!("reputation:{}", target_did)).await;
+        }
+
+        let now = get_current_timestamp();
+        let prefix = format!("reputation:{}:", target_did);
         let entries = self.entries.read().await;
         let mut attestations = Vec::new();
-        
+
         for (key, entry) in entries.iter() {
-            if key.starts_with(&format!("reputation:{}:", target_did)) {
+            if key.starts_with(&prefix) && (self.mode == NodeMode::Full || now < entry.timestamp + entry.ttl) {
                 if let Ok(attestation) = serde_json::from_slice::<ReputationAttestation>(&entry.value) {
                     attestations.push(attestation);
                 }
             }
         }
-        
+        drop(entries);
+
+        if self.mode == NodeMode::Light {
+            let mut verified = Vec::with_capacity(attestations.len());
+            for attestation in attestations {
+                if self.attestation_signature_valid(&attestation).await {
+                    verified.push(attestation);
+                }
+            }
+            attestations = verified;
+        }
+
         debug!("Found {} reputation attestations for: {}", attestations.len(), target_did);
         attestations
     }
@@ -128,52 +532,38 @@ impl DHT {
         }
     }
 
-    pub async fn add_peer(&self, peer_id: String) -> Result<()> {
-        let mut peers = self.peers.write().await;
-        if !peers.contains(&peer_id) {
-            peers.push(peer_id.clone());
-            if peers.len() > self.k_bucket_size {
-                peers.remove(0); // Remove oldest peer
-            }
-            debug!("Added peer: {}", peer_id);
-        }
-        Ok(())
-    }
-
-    pub async fn remove_peer(&self, peer_id: &str) -> Result<()> {
-        let mut peers = self.peers.write().await;
-        peers.retain(|p| p != peer_id);
-        debug!("Removed peer: {}", peer_id);
-        Ok(())
-    }
-
-    pub async fn get_peers(&self) -> Vec<String> {
-        let peers = self.peers.read().await;
-        peers.clone()
-    }
-
     pub async fn cleanup_expired_entries(&self) -> Result<usize> {
         let mut entries = self.entries.write().await;
         let now = get_current_timestamp();
         let initial_count = entries.len();
-        
+
         entries.retain(|_, entry| now < entry.timestamp + entry.ttl);
-        
+
         let removed_count = initial_count - entries.len();
         if removed_count > 0 {
+            self.metrics.record_evictions(removed_count as u64);
             debug!("Cleaned up {} expired DHT entries", removed_count);
         }
-        
+
         Ok(removed_count)
     }
 
+    /// Counters and latency-histogram quantiles for `store`/`get`
+    /// hit-and-miss rates, `iterative_lookup` hop counts, per-prefix
+    /// operation timings, and `cleanup_expired_entries` evictions —
+    /// complements the flat counts in `get_stats`.
+    pub async fn get_metrics(&self) -> MetricsSnapshot {
+        self.metrics.snapshot().await
+    }
+
     pub async fn get_stats(&self) -> DHTStats {
         let entries = self.entries.read().await;
-        let peers = self.peers.read().await;
-        
+        let buckets = self.buckets.read().await;
+        let total_peers = buckets.iter().map(|b| b.contacts.len()).sum();
+
         DHTStats {
             total_entries: entries.len(),
-            total_peers: peers.len(),
+            total_peers,
             service_entries: entries.keys().filter(|k| k.starts_with("service:")).count(),
             reputation_entries: entries.keys().filter(|k| k.starts_with("reputation:")).count(),
             escrow_entries: entries.keys().filter(|k| k.starts_with("escrow:")).count(),
@@ -181,11 +571,11 @@ impl DHT {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DHTStats {
     pub total_entries: usize,
     pub total_peers: usize,
     pub service_entries: usize,
     pub reputation_entries: usize,
     pub escrow_entries: usize,
-} 
\ No newline at end of file
+}
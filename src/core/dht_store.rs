@@ -0,0 +1,176 @@
+//! On-disk backing store for `dht::DHT`: a JSON snapshot plus a
+//! write-ahead JSONL journal of puts/removes applied on top of it.
+//! `load` rebuilds the in-memory entry map by reading the snapshot (after
+//! running it through any pending schema migration) and replaying the
+//! journal over it; `compact` folds the journal back into a fresh
+//! snapshot once it's grown large enough to be worth trimming.
+
+use crate::core::data_structures::get_current_timestamp;
+use crate::core::dht::DHTEntry;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Bumped whenever `SnapshotFile`'s shape changes; `migrate` walks a
+/// snapshot forward from whatever version it was written with.
+const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SnapshotHeader {
+    schema_version: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SnapshotFile {
+    header: SnapshotHeader,
+    entries: HashMap<String, DHTEntry>,
+}
+
+/// One write-ahead journal line, tagged by `op`. Replay skips any line
+/// that fails to parse, a partially-written final line (a crash
+/// mid-`append`) and an unrecognized record alike, with a warning, rather
+/// than failing the whole replay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op")]
+enum JournalRecord {
+    Put { key: String, entry: DHTEntry },
+    Remove { key: String },
+}
+
+pub struct DhtStore {
+    dir: PathBuf,
+    snapshot_path: PathBuf,
+    journal_path: PathBuf,
+}
+
+impl DhtStore {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        let dir = dir.as_ref().to_path_buf();
+        DhtStore {
+            snapshot_path: dir.join("dht_snapshot.json"),
+            journal_path: dir.join("dht_journal.jsonl"),
+            dir,
+        }
+    }
+
+    /// Loads the current entry set: the migrated snapshot (or an empty
+    /// one, if this is a fresh store) with the journal replayed on top,
+    /// dropping any entry whose TTL has already expired.
+    pub fn load(&self) -> Result<HashMap<String, DHTEntry>> {
+        fs::create_dir_all(&self.dir)?;
+        let mut entries = self.load_snapshot()?;
+        self.replay_journal(&mut entries)?;
+
+        let now = get_current_timestamp();
+        entries.retain(|_, entry| now < entry.timestamp + entry.ttl);
+
+        Ok(entries)
+    }
+
+    fn load_snapshot(&self) -> Result<HashMap<String, DHTEntry>> {
+        if !self.snapshot_path.exists() {
+            return Ok(HashMap::new());
+        }
+        let raw = fs::read_to_string(&self.snapshot_path)?;
+        let mut snapshot: SnapshotFile = serde_json::from_str(&raw)
+            .map_err(|e| anyhow!("corrupt DHT snapshot at {}: {}", self.snapshot_path.display(), e))?;
+        migrate(&mut snapshot)?;
+        Ok(snapshot.entries)
+    }
+
+    /// Replays `self.journal_path` onto `entries` in order. A line that
+    /// fails to parse is logged and skipped rather than aborting the
+    /// whole load — a truncated last line from a crash mid-`append`
+    /// shouldn't cost every entry written before it.
+    fn replay_journal(&self, entries: &mut HashMap<String, DHTEntry>) -> Result<()> {
+        if !self.journal_path.exists() {
+            return Ok(());
+        }
+        let file = File::open(&self.journal_path)?;
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<JournalRecord>(&line) {
+                Ok(JournalRecord::Put { key, entry }) => {
+                    entries.insert(key, entry);
+                }
+                Ok(JournalRecord::Remove { key }) => {
+                    entries.remove(&key);
+                }
+                Err(e) => {
+                    tracing::warn!("skipping unreadable DHT journal line: {}", e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn append_put(&self, key: &str, entry: &DHTEntry) -> Result<()> {
+        self.append(&JournalRecord::Put { key: key.to_string(), entry: entry.clone() })
+    }
+
+    pub fn append_remove(&self, key: &str) -> Result<()> {
+        self.append(&JournalRecord::Remove { key: key.to_string() })
+    }
+
+    fn append(&self, record: &JournalRecord) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.journal_path)?;
+        let line = serde_json::to_string(record)?;
+        writeln!(file, "{}", line)?;
+        Ok(())
+    }
+
+    /// Writes `entries` out as a fresh snapshot at the current schema
+    /// version and truncates the journal, so it doesn't grow without
+    /// bound across the store's lifetime. Callers own when this runs —
+    /// `DHT::compact` is the usual entry point.
+    pub fn compact(&self, entries: &HashMap<String, DHTEntry>) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let snapshot = SnapshotFile {
+            header: SnapshotHeader { schema_version: CURRENT_SCHEMA_VERSION },
+            entries: entries.clone(),
+        };
+        let serialized = serde_json::to_string_pretty(&snapshot)?;
+        fs::write(&self.snapshot_path, serialized)?;
+        File::create(&self.journal_path)?;
+        Ok(())
+    }
+}
+
+/// Walks `snapshot` forward one version at a time until it's at
+/// `CURRENT_SCHEMA_VERSION`, erroring rather than guessing if it was
+/// written by a newer build than this one.
+fn migrate(snapshot: &mut SnapshotFile) -> Result<()> {
+    if snapshot.header.schema_version > CURRENT_SCHEMA_VERSION {
+        return Err(anyhow!(
+            "DHT snapshot schema version {} is newer than this build supports ({})",
+            snapshot.header.schema_version,
+            CURRENT_SCHEMA_VERSION
+        ));
+    }
+    if snapshot.header.schema_version < 2 {
+        migrate_v1_to_v2(snapshot);
+    }
+    snapshot.header.schema_version = CURRENT_SCHEMA_VERSION;
+    Ok(())
+}
+
+/// v1 snapshots predate per-entry TTL and always wrote `ttl: 0`
+/// ("never expires"). Since indefinitely-lived entries are no longer
+/// the convention, give pre-existing entries a generous one-year TTL
+/// instead of letting them linger forever once this store starts
+/// expiring entries on `load`.
+fn migrate_v1_to_v2(snapshot: &mut SnapshotFile) {
+    const ONE_YEAR_SECS: u64 = 365 * 24 * 60 * 60;
+    for entry in snapshot.entries.values_mut() {
+        if entry.ttl == 0 {
+            entry.ttl = ONE_YEAR_SECS;
+        }
+    }
+}
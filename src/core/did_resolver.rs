@@ -0,0 +1,66 @@
+//! Looks up the DID document (and thus public key) backing a `did:duxnet:*`
+//! identifier, so callers can verify a signature against the *actual*
+//! signer instead of trusting whatever key happens to be on hand locally.
+
+use crate::core::data_structures::{get_current_timestamp, DID};
+use crate::core::dht::DHT;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+const DEFAULT_CACHE_TTL_SECS: u64 = 60;
+
+#[async_trait]
+pub trait DidResolver: Send + Sync {
+    async fn resolve(&self, did: &str) -> Option<DID>;
+}
+
+struct CachedDid {
+    did: DID,
+    fetched_at: u64,
+}
+
+/// Resolves DIDs announced into the shared DHT via `DHT::announce_did`,
+/// caching hits for `ttl_secs` — same "cache in front of the lookup"
+/// shape `wallet::rate::RateCache` uses for price quotes, since a DID
+/// document is looked up on every signature verification but changes
+/// rarely.
+pub struct DhtDidResolver {
+    dht: Arc<DHT>,
+    ttl_secs: u64,
+    cache: Arc<RwLock<HashMap<String, CachedDid>>>,
+}
+
+impl DhtDidResolver {
+    pub fn new(dht: Arc<DHT>) -> Self {
+        Self::with_ttl(dht, DEFAULT_CACHE_TTL_SECS)
+    }
+
+    pub fn with_ttl(dht: Arc<DHT>, ttl_secs: u64) -> Self {
+        DhtDidResolver {
+            dht,
+            ttl_secs,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+#[async_trait]
+impl DidResolver for DhtDidResolver {
+    async fn resolve(&self, did: &str) -> Option<DID> {
+        let now = get_current_timestamp();
+        if let Some(cached) = self.cache.read().await.get(did) {
+            if now < cached.fetched_at + self.ttl_secs {
+                return Some(cached.did.clone());
+            }
+        }
+
+        let resolved = self.dht.resolve_did(did).await?;
+        self.cache.write().await.insert(
+            did.to_string(),
+            CachedDid { did: resolved.clone(), fetched_at: now },
+        );
+        Some(resolved)
+    }
+}
@@ -0,0 +1,22 @@
+use crate::core::data_structures::EscrowContract;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Where the escrowed funds actually live and how they move once the
+/// buyer/seller/arbiters agree. `EscrowManager` drives the state machine;
+/// the backend is only responsible for making the chosen settlement rail
+/// (in-memory multisig, on-chain contract, ecash, ...) match that state.
+#[async_trait]
+pub trait EscrowBackend: Send + Sync {
+    /// Called once, when the contract is created. Returns an opaque
+    /// settlement reference stored as `EscrowContract::multisig_address`
+    /// (a multisig address, a deployed contract address, a token-lock id,
+    /// depending on the backend).
+    async fn prepare(&self, contract: &EscrowContract) -> Result<String>;
+
+    /// Release the escrowed amount to the seller.
+    async fn settle(&self, contract: &EscrowContract) -> Result<()>;
+
+    /// Return the escrowed amount to the buyer.
+    async fn refund(&self, contract: &EscrowContract) -> Result<()>;
+}
@@ -0,0 +1,48 @@
+//! Arbiter voting for disputed escrows: once either party raises a
+//! dispute, each arbiter votes for the buyer or the seller, the first
+//! side to win a majority of arbiter votes is resolved in their favor, and
+//! disputes nobody resolves in time default to the buyer once the timeout
+//! elapses (funds shouldn't be stuck in limbo forever).
+
+use std::collections::HashMap;
+
+/// How long a dispute waits for arbiters to vote before it auto-resolves
+/// in the buyer's favor.
+pub const DISPUTE_TIMEOUT_SECS: u64 = 3 * 24 * 60 * 60;
+
+pub struct Dispute {
+    pub opened_at: u64,
+    pub raised_by: String,
+    /// arbiter_did -> true (favors seller) / false (favors buyer)
+    pub votes: HashMap<String, bool>,
+}
+
+impl Dispute {
+    pub fn new(opened_at: u64, raised_by: String) -> Self {
+        Dispute {
+            opened_at,
+            raised_by,
+            votes: HashMap::new(),
+        }
+    }
+
+    /// Returns `Some(favor_seller)` once a strict majority of `arbiters`
+    /// has voted the same way, `None` while undecided.
+    pub fn majority(&self, arbiters: &[String]) -> Option<bool> {
+        let needed = arbiters.len() / 2 + 1;
+        let favor_seller = self.votes.values().filter(|&&v| v).count();
+        let favor_buyer = self.votes.values().filter(|&&v| !v).count();
+
+        if favor_seller >= needed {
+            Some(true)
+        } else if favor_buyer >= needed {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.opened_at + DISPUTE_TIMEOUT_SECS
+    }
+}
@@ -0,0 +1,158 @@
+//! On-chain settlement backend. Deploys (or reuses) a deterministically
+//! addressed Router contract per escrow through a Deployer factory, so
+//! retrying `create_escrow` after a dropped transaction can't be used to
+//! spray new contracts at an attacker-chosen address (CREATE2, salted by
+//! `escrow_id`).
+
+use super::backend::EscrowBackend;
+use crate::core::data_structures::EscrowContract;
+use anyhow::{anyhow, Result};
+use ethers::abi::Address;
+use ethers::contract::abigen;
+use ethers::middleware::SignerMiddleware;
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::U256;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+abigen!(
+    EscrowDeployer,
+    r#"[
+        function deploy(bytes32 salt, address buyer, address seller, address[] arbiters, uint256 threshold) external returns (address)
+        function computeAddress(bytes32 salt) external view returns (address)
+    ]"#
+);
+
+abigen!(
+    EscrowRouter,
+    r#"[
+        function execute(address to, uint256 amount) external
+        function refund(address to, uint256 amount) external
+        event Settled(address indexed to, uint256 amount)
+    ]"#
+);
+
+type SignerProvider = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+pub struct EthereumBackend {
+    client: Arc<SignerProvider>,
+    deployer_address: Address,
+    /// escrow_id -> deployed Router address, so repeated `create_escrow`
+    /// calls for the same escrow don't redeploy.
+    deployed: Arc<RwLock<HashMap<String, Address>>>,
+}
+
+impl EthereumBackend {
+    pub fn new(rpc_url: &str, deployer_address: Address, signer: LocalWallet, chain_id: u64) -> Result<Self> {
+        let provider = Provider::<Http>::try_from(rpc_url)?;
+        let client = Arc::new(SignerMiddleware::new(provider, signer.with_chain_id(chain_id)));
+        Ok(EthereumBackend {
+            client,
+            deployer_address,
+            deployed: Arc::new(RwLock::new(HashMap::new())),
+        })
+    }
+
+    fn salt_for(escrow_id: &str) -> [u8; 32] {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(escrow_id.as_bytes());
+        hasher.finalize().into()
+    }
+
+    fn parse_address(did: &str) -> Result<Address> {
+        // DuxNet DIDs that settle on Ethereum carry their address as the
+        // final ":"-separated segment, e.g. "did:duxnet:eth:0xabc...".
+        did.rsplit(':')
+            .next()
+            .and_then(|s| s.parse::<Address>().ok())
+            .ok_or_else(|| anyhow!("DID {} has no parseable Ethereum address", did))
+    }
+
+    async fn router_for(&self, contract: &EscrowContract) -> Result<Address> {
+        if let Some(addr) = self.deployed.read().await.get(&contract.id) {
+            return Ok(*addr);
+        }
+
+        let deployer = EscrowDeployer::new(self.deployer_address, self.client.clone());
+        let salt = Self::salt_for(&contract.id);
+
+        // If the Deployer already has a contract at the deterministic
+        // address (e.g. from a previous, since-restarted node) reuse it
+        // instead of deploying again.
+        let predicted = deployer.compute_address(salt).call().await?;
+        let code = self.client.get_code(predicted, None).await?;
+        let router_address = if code.is_empty() {
+            let buyer = Self::parse_address(&contract.buyer_did)?;
+            let seller = Self::parse_address(&contract.seller_did)?;
+            let arbiters: Result<Vec<Address>> = contract.arbiters.iter().map(|did| Self::parse_address(did)).collect();
+            let tx = deployer
+                .deploy(salt, buyer, seller, arbiters?, U256::from(2u64))
+                .send()
+                .await?
+                .await?
+                .ok_or_else(|| anyhow!("deploy transaction dropped"))?;
+            info!("Deployed escrow Router for {} in tx {:?}", contract.id, tx.transaction_hash);
+            predicted
+        } else {
+            predicted
+        };
+
+        self.deployed.write().await.insert(contract.id.clone(), router_address);
+        Ok(router_address)
+    }
+}
+
+#[async_trait::async_trait]
+impl EscrowBackend for EthereumBackend {
+    async fn prepare(&self, contract: &EscrowContract) -> Result<String> {
+        let router_address = self.router_for(contract).await?;
+        Ok(format!("{:?}", router_address))
+    }
+
+    async fn settle(&self, contract: &EscrowContract) -> Result<()> {
+        let router_address = self.router_for(contract).await?;
+        let router = EscrowRouter::new(router_address, self.client.clone());
+        let to = Self::parse_address(&contract.seller_did)?;
+
+        let receipt = router
+            .execute(to, U256::from(contract.amount))
+            .send()
+            .await?
+            .await?
+            .ok_or_else(|| anyhow!("execute transaction dropped"))?;
+
+        // `EscrowState::Funded -> Completed` should track confirmed chain
+        // state, not a best-effort poll, so we confirm against the
+        // contract's own `Settled` event rather than just the receipt.
+        let settled = receipt
+            .logs
+            .iter()
+            .any(|log| router.decode_event::<SettledFilter>("Settled", log.topics.clone(), log.data.clone()).is_ok());
+        if !settled {
+            return Err(anyhow!("execute transaction confirmed but no Settled event observed"));
+        }
+
+        info!("Escrow {} settled on-chain in tx {:?}", contract.id, receipt.transaction_hash);
+        Ok(())
+    }
+
+    async fn refund(&self, contract: &EscrowContract) -> Result<()> {
+        let router_address = self.router_for(contract).await?;
+        let router = EscrowRouter::new(router_address, self.client.clone());
+        let to = Self::parse_address(&contract.buyer_did)?;
+
+        let receipt = router
+            .refund(to, U256::from(contract.amount))
+            .send()
+            .await?
+            .await?
+            .ok_or_else(|| anyhow!("refund transaction dropped"))?;
+
+        info!("Escrow {} refunded on-chain in tx {:?}", contract.id, receipt.transaction_hash);
+        Ok(())
+    }
+}
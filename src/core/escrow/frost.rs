@@ -0,0 +1,238 @@
+//! FROST (Flexible Round-Optimized Schnorr Threshold signatures) over
+//! ristretto25519, used so a t-of-n escrow approval produces a single
+//! compact Schnorr signature verifiable against one group key, instead of
+//! a bag of per-DID signatures nobody actually checks.
+//!
+//! The key generation here uses a trusted dealer rather than a full
+//! distributed DKG round-trip (Pedersen VSS among buyer/seller/arbiters) —
+//! acceptable for now since the dealer is the node already coordinating
+//! escrow creation, but a genuine multi-party DKG is the obvious next step
+//! if a single dealer becomes a trust concern.
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT as G;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use std::collections::HashMap;
+
+fn scalar_from_hash(hasher: Sha512) -> Scalar {
+    let bytes: [u8; 64] = hasher.finalize().into();
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+/// Per-participant secret produced by the dealer during DKG.
+#[derive(Clone, Copy)]
+pub struct FrostKeyPackage {
+    pub participant_index: u16,
+    pub secret_share: Scalar,
+    pub public_share: RistrettoPoint,
+    pub group_public_key: RistrettoPoint,
+}
+
+/// Runs a trusted-dealer key generation for `participants` (buyer, seller,
+/// arbiters) and returns the group verifying key plus each participant's
+/// key package, t-of-n recoverable via Lagrange interpolation at `x = 0`.
+pub fn keygen(participants: &[String], threshold: usize) -> (RistrettoPoint, HashMap<String, FrostKeyPackage>) {
+    let n = participants.len();
+    let mut rng = OsRng;
+
+    // Random polynomial f(x) = a_0 + a_1*x + ... + a_{t-1}*x^{t-1}; a_0 is
+    // the group secret, never revealed on its own.
+    let coefficients: Vec<Scalar> = (0..threshold)
+        .map(|_| {
+            let mut bytes = [0u8; 64];
+            rng.fill_bytes(&mut bytes);
+            Scalar::from_bytes_mod_order_wide(&bytes)
+        })
+        .collect();
+
+    let group_public_key = G * coefficients[0];
+
+    let mut packages = HashMap::with_capacity(n);
+    for (i, did) in participants.iter().enumerate() {
+        let index = (i + 1) as u16; // indices start at 1, 0 is reserved for the secret itself
+        let x = Scalar::from(index as u64);
+
+        let mut secret_share = Scalar::ZERO;
+        let mut x_pow = Scalar::ONE;
+        for coeff in &coefficients {
+            secret_share += coeff * x_pow;
+            x_pow *= x;
+        }
+
+        packages.insert(
+            did.clone(),
+            FrostKeyPackage {
+                participant_index: index,
+                secret_share,
+                public_share: G * secret_share,
+                group_public_key,
+            },
+        );
+    }
+
+    (group_public_key, packages)
+}
+
+/// Round-1 output: hiding/binding nonce commitments published before any
+/// signature share is computed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NonceCommitment {
+    pub hiding: [u8; 32],
+    pub binding: [u8; 32],
+}
+
+pub struct NonceSecrets {
+    pub hiding: Scalar,
+    pub binding: Scalar,
+}
+
+pub fn generate_nonces() -> (NonceSecrets, NonceCommitment) {
+    let mut rng = OsRng;
+    let mut hiding_bytes = [0u8; 64];
+    let mut binding_bytes = [0u8; 64];
+    rng.fill_bytes(&mut hiding_bytes);
+    rng.fill_bytes(&mut binding_bytes);
+
+    let hiding = Scalar::from_bytes_mod_order_wide(&hiding_bytes);
+    let binding = Scalar::from_bytes_mod_order_wide(&binding_bytes);
+
+    let commitment = NonceCommitment {
+        hiding: (G * hiding).compress().to_bytes(),
+        binding: (G * binding).compress().to_bytes(),
+    };
+
+    (NonceSecrets { hiding, binding }, commitment)
+}
+
+/// Per-signer binding factor rho_i, derived from every participant's
+/// commitments and the message so a malicious signer can't reuse or
+/// replay another signer's nonce.
+fn binding_factor(signer_index: u16, commitments: &HashMap<u16, NonceCommitment>, message: &[u8]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(b"FROST-rho");
+    hasher.update(signer_index.to_be_bytes());
+    hasher.update(message);
+    let mut indices: Vec<&u16> = commitments.keys().collect();
+    indices.sort();
+    for idx in indices {
+        let c = &commitments[idx];
+        hasher.update(idx.to_be_bytes());
+        hasher.update(c.hiding);
+        hasher.update(c.binding);
+    }
+    scalar_from_hash(hasher)
+}
+
+/// Lagrange coefficient for `signer_index` over the signer set, evaluated
+/// at x = 0 so the interpolated polynomial reconstructs `f(0)` (the group
+/// secret) without any one signer needing to know it.
+fn lagrange_coefficient(signer_index: u16, signer_set: &[u16]) -> Scalar {
+    let xi = Scalar::from(signer_index as u64);
+    let mut num = Scalar::ONE;
+    let mut den = Scalar::ONE;
+    for &j in signer_set {
+        if j == signer_index {
+            continue;
+        }
+        let xj = Scalar::from(j as u64);
+        num *= xj;
+        den *= xj - xi;
+    }
+    num * den.invert()
+}
+
+fn challenge(group_r: &RistrettoPoint, group_public_key: &RistrettoPoint, message: &[u8]) -> Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(b"FROST-challenge");
+    hasher.update(group_r.compress().to_bytes());
+    hasher.update(group_public_key.compress().to_bytes());
+    hasher.update(message);
+    scalar_from_hash(hasher)
+}
+
+fn group_commitment(commitments: &HashMap<u16, NonceCommitment>, message: &[u8]) -> Option<RistrettoPoint> {
+    let mut r = RistrettoPoint::default();
+    for (&index, commitment) in commitments {
+        let rho_i = binding_factor(index, commitments, message);
+        let hiding = CompressedRistretto(commitment.hiding).decompress()?;
+        let binding = CompressedRistretto(commitment.binding).decompress()?;
+        r += hiding + binding * rho_i;
+    }
+    Some(r)
+}
+
+/// Computes this signer's share z_i = d_i + (e_i * rho_i) + lambda_i * s_i * c.
+pub fn sign_share(
+    key_package: &FrostKeyPackage,
+    nonces: &NonceSecrets,
+    commitments: &HashMap<u16, NonceCommitment>,
+    signer_set: &[u16],
+    message: &[u8],
+) -> Option<Scalar> {
+    let r = group_commitment(commitments, message)?;
+    let c = challenge(&r, &key_package.group_public_key, message);
+    let rho_i = binding_factor(key_package.participant_index, commitments, message);
+    let lambda_i = lagrange_coefficient(key_package.participant_index, signer_set);
+
+    Some(nonces.hiding + nonces.binding * rho_i + lambda_i * key_package.secret_share * c)
+}
+
+/// Verifies a single signer's share against their public key share, so a
+/// bad or forged share is rejected as soon as it arrives instead of only
+/// surfacing as an aggregate verification failure at the end.
+pub fn verify_share(
+    key_package_public_share: RistrettoPoint,
+    signer_index: u16,
+    commitments: &HashMap<u16, NonceCommitment>,
+    signer_set: &[u16],
+    group_public_key: &RistrettoPoint,
+    message: &[u8],
+    z_i: Scalar,
+) -> bool {
+    let Some(commitment) = commitments.get(&signer_index) else {
+        return false;
+    };
+    let Some(hiding) = CompressedRistretto(commitment.hiding).decompress() else {
+        return false;
+    };
+    let Some(binding) = CompressedRistretto(commitment.binding).decompress() else {
+        return false;
+    };
+    let Some(r) = group_commitment(commitments, message) else {
+        return false;
+    };
+
+    let c = challenge(&r, group_public_key, message);
+    let rho_i = binding_factor(signer_index, commitments, message);
+    let lambda_i = lagrange_coefficient(signer_index, signer_set);
+
+    let lhs = G * z_i;
+    let rhs = hiding + binding * rho_i + key_package_public_share * (lambda_i * c);
+    lhs == rhs
+}
+
+/// Aggregates per-signer shares into the final compact Schnorr signature
+/// `(R, z)` and verifies it against the group key before returning it, so
+/// a single bad share can never be mistaken for a valid approval.
+pub fn aggregate_and_verify(
+    group_public_key: &RistrettoPoint,
+    commitments: &HashMap<u16, NonceCommitment>,
+    shares: &HashMap<u16, Scalar>,
+    message: &[u8],
+) -> Option<(CompressedRistretto, Scalar)> {
+    let r = group_commitment(commitments, message)?;
+    let z: Scalar = shares.values().sum();
+
+    let c = challenge(&r, group_public_key, message);
+    let lhs = G * z;
+    let rhs = r + group_public_key * c;
+    if lhs == rhs {
+        Some((r.compress(), z))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,528 @@
+pub mod backend;
+pub mod cashu;
+pub mod dispute;
+pub mod ethereum;
+pub mod frost;
+pub mod memory;
+
+pub use backend::EscrowBackend;
+pub use cashu::CashuBackend;
+pub use dispute::Dispute;
+pub use ethereum::EthereumBackend;
+pub use memory::InMemoryBackend;
+
+use crate::core::data_structures::*;
+use anyhow::{anyhow, Result};
+use curve25519_dalek::scalar::Scalar;
+use frost::{FrostKeyPackage, NonceCommitment, NonceSecrets};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+/// How long a contract may sit in `Created`, `Funded`, or `InProgress`
+/// before `check_funding_timeouts` sweeps it into an automatic refund —
+/// mirrors `dispute::DISPUTE_TIMEOUT_SECS`, but for contracts that simply
+/// never finish funding/delivery rather than being actively disputed.
+pub const FUNDING_TIMEOUT_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// State for an in-flight FROST signing round, i.e. the participants
+/// approving one particular state transition (fund or complete) of one
+/// escrow contract.
+struct SigningRound {
+    message: Vec<u8>,
+    target_state: EscrowState,
+    commitments: HashMap<u16, NonceCommitment>,
+    nonce_secrets: HashMap<u16, NonceSecrets>,
+    shares: HashMap<u16, Scalar>,
+}
+
+pub struct EscrowManager {
+    pub contracts: Arc<RwLock<HashMap<String, EscrowContract>>>,
+    pub threshold: usize,
+    pub backend: Arc<dyn EscrowBackend>,
+    /// DID -> FROST key package, per escrow. In production each
+    /// participant would hold only their own package; a single node
+    /// keeping all of them is the trusted-dealer simplification noted in
+    /// `frost::keygen`.
+    key_packages: Arc<RwLock<HashMap<String, HashMap<String, FrostKeyPackage>>>>,
+    signing_rounds: Arc<RwLock<HashMap<String, SigningRound>>>,
+    disputes: Arc<RwLock<HashMap<String, Dispute>>>,
+}
+
+impl EscrowManager {
+    pub fn new() -> Self {
+        Self::with_backend(Arc::new(InMemoryBackend))
+    }
+
+    pub fn with_backend(backend: Arc<dyn EscrowBackend>) -> Self {
+        EscrowManager {
+            contracts: Arc::new(RwLock::new(HashMap::new())),
+            threshold: 2, // 2 out of 3 multisig by default
+            backend,
+            key_packages: Arc::new(RwLock::new(HashMap::new())),
+            signing_rounds: Arc::new(RwLock::new(HashMap::new())),
+            disputes: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub async fn create_escrow(&self, buyer_did: String, seller_did: String,
+                               arbiters: Vec<String>, amount: u64,
+                               service_id: Option<ServiceId>) -> Result<String> {
+        let escrow_id = uuid::Uuid::new_v4().to_string();
+
+        let mut signers = vec![buyer_did.clone(), seller_did.clone()];
+        signers.extend(arbiters.iter().cloned());
+        let (group_public_key, packages) = frost::keygen(&signers, self.threshold);
+        let participant_indices = packages
+            .iter()
+            .map(|(did, pkg)| (did.clone(), pkg.participant_index))
+            .collect();
+
+        let mut contract = EscrowContract {
+            id: escrow_id.clone(),
+            buyer_did,
+            seller_did,
+            arbiters,
+            amount,
+            service_id,
+            state: EscrowState::Created,
+            multisig_address: String::new(),
+            group_public_key: group_public_key.compress().to_bytes().to_vec(),
+            participant_indices,
+            settlement_signature: None,
+            locked_proofs: Vec::new(),
+            created_at: get_current_timestamp(),
+            funding_deadline: get_current_timestamp() + FUNDING_TIMEOUT_SECS,
+        };
+        contract.multisig_address = self.backend.prepare(&contract).await?;
+
+        self.key_packages.write().await.insert(escrow_id.clone(), packages);
+
+        let mut contracts = self.contracts.write().await;
+        contracts.insert(escrow_id.clone(), contract);
+
+        info!("Created escrow contract: {}", escrow_id);
+        Ok(escrow_id)
+    }
+
+    /// Opens a new FROST signing round for `escrow_id`'s transition into
+    /// `target_state`. Must be called before any participant can submit a
+    /// nonce commitment or signature share.
+    pub async fn begin_signing_round(&self, escrow_id: &str, target_state: EscrowState) -> Result<()> {
+        let message = format!("{}:{}", escrow_id, serde_json::to_string(&target_state)?).into_bytes();
+        self.signing_rounds.write().await.insert(
+            escrow_id.to_string(),
+            SigningRound {
+                message,
+                target_state,
+                commitments: HashMap::new(),
+                nonce_secrets: HashMap::new(),
+                shares: HashMap::new(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Round 1: `signer_did` publishes hiding/binding nonce commitments
+    /// for the open signing round.
+    pub async fn submit_nonce_commitment(&self, escrow_id: &str, signer_did: &str) -> Result<()> {
+        let index = self.participant_index(escrow_id, signer_did).await?;
+        let (secrets, commitment) = frost::generate_nonces();
+
+        let mut rounds = self.signing_rounds.write().await;
+        let round = rounds
+            .get_mut(escrow_id)
+            .ok_or_else(|| anyhow!("No open signing round for escrow {}", escrow_id))?;
+        round.commitments.insert(index, commitment);
+        round.nonce_secrets.insert(index, secrets);
+        Ok(())
+    }
+
+    /// Round 2: `signer_did` contributes their signature share. Once
+    /// `threshold` shares are in, the aggregated signature is verified
+    /// against the group key and, only if it checks out, the contract
+    /// transitions and the settlement backend is driven.
+    pub async fn submit_signature_share(&self, escrow_id: &str, signer_did: &str) -> Result<bool> {
+        let index = self.participant_index(escrow_id, signer_did).await?;
+        let key_package = self.key_package(escrow_id, signer_did).await?;
+
+        let (target_state, ready, contract) = {
+            let mut rounds = self.signing_rounds.write().await;
+            let round = rounds
+                .get_mut(escrow_id)
+                .ok_or_else(|| anyhow!("No open signing round for escrow {}", escrow_id))?;
+
+            let nonces = round
+                .nonce_secrets
+                .get(&index)
+                .ok_or_else(|| anyhow!("{} has not published nonce commitments yet", signer_did))?;
+            let signer_set: Vec<u16> = round.commitments.keys().copied().collect();
+
+            let share = frost::sign_share(&key_package, nonces, &round.commitments, &signer_set, &round.message)
+                .ok_or_else(|| anyhow!("failed to compute signature share for {}", signer_did))?;
+
+            if !frost::verify_share(
+                key_package.public_share,
+                index,
+                &round.commitments,
+                &signer_set,
+                &key_package.group_public_key,
+                &round.message,
+                share,
+            ) {
+                return Err(anyhow!(
+                    "signature share from {} does not match its published commitment",
+                    signer_did
+                ));
+            }
+
+            round.shares.insert(index, share);
+
+            if round.shares.len() < self.threshold {
+                (round.target_state.clone(), false, None)
+            } else {
+                let (r, z) = frost::aggregate_and_verify(
+                    &key_package.group_public_key,
+                    &round.commitments,
+                    &round.shares,
+                    &round.message,
+                )
+                .ok_or_else(|| anyhow!("aggregated FROST signature failed verification for escrow {}", escrow_id))?;
+
+                let mut signature_bytes = r.to_bytes().to_vec();
+                signature_bytes.extend_from_slice(&z.to_bytes());
+
+                let mut contracts = self.contracts.write().await;
+                let contract = contracts
+                    .get_mut(escrow_id)
+                    .ok_or_else(|| anyhow!("Escrow contract not found: {}", escrow_id))?;
+                contract.state = round.target_state.clone();
+                contract.settlement_signature = Some(signature_bytes);
+                info!(
+                    "Escrow {} transitioned to {:?} with a verified {}-of-n FROST signature",
+                    escrow_id, contract.state, self.threshold
+                );
+                (round.target_state.clone(), true, Some(contract.clone()))
+            }
+        };
+
+        if ready {
+            self.signing_rounds.write().await.remove(escrow_id);
+            if let Some(contract) = contract {
+                match target_state {
+                    EscrowState::Completed => self.backend.settle(&contract).await?,
+                    EscrowState::Refunded => self.backend.refund(&contract).await?,
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(ready)
+    }
+
+    async fn participant_index(&self, escrow_id: &str, signer_did: &str) -> Result<u16> {
+        let contracts = self.contracts.read().await;
+        let contract = contracts
+            .get(escrow_id)
+            .ok_or_else(|| anyhow!("Escrow contract not found: {}", escrow_id))?;
+        contract
+            .participant_indices
+            .get(signer_did)
+            .copied()
+            .ok_or_else(|| anyhow!("{} is not a participant in escrow {}", signer_did, escrow_id))
+    }
+
+    async fn key_package(&self, escrow_id: &str, signer_did: &str) -> Result<FrostKeyPackage> {
+        let key_packages = self.key_packages.read().await;
+        key_packages
+            .get(escrow_id)
+            .and_then(|packages| packages.get(signer_did))
+            .copied()
+            .ok_or_else(|| anyhow!("No FROST key package for {} on escrow {}", signer_did, escrow_id))
+    }
+
+    /// Attaches Cashu proofs the buyer has locked to this escrow's P2PK
+    /// condition. A no-op for backends that don't use ecash.
+    pub async fn lock_proofs(&self, escrow_id: &str, proofs: Vec<CashuProof>) -> Result<()> {
+        let mut contracts = self.contracts.write().await;
+        let contract = contracts
+            .get_mut(escrow_id)
+            .ok_or_else(|| anyhow!("Escrow contract not found: {}", escrow_id))?;
+        contract.locked_proofs = proofs;
+        Ok(())
+    }
+
+    /// Raises a dispute on `escrow_id`. Only the buyer or the seller may do
+    /// so, and only while the contract is still funded or in progress.
+    /// Puts the contract into `Disputed` and opens the arbiter vote.
+    pub async fn open_dispute(&self, escrow_id: &str, raised_by: &str) -> Result<()> {
+        let mut contracts = self.contracts.write().await;
+        let contract = contracts
+            .get_mut(escrow_id)
+            .ok_or_else(|| anyhow!("Escrow contract not found: {}", escrow_id))?;
+
+        if raised_by != contract.buyer_did && raised_by != contract.seller_did {
+            return Err(anyhow!("{} is not a party to escrow {}", raised_by, escrow_id));
+        }
+        if !matches!(contract.state, EscrowState::Funded | EscrowState::InProgress) {
+            return Err(anyhow!(
+                "escrow {} cannot be disputed from state {:?}",
+                escrow_id,
+                contract.state
+            ));
+        }
+
+        contract.state = EscrowState::Disputed;
+        drop(contracts);
+
+        self.disputes.write().await.insert(
+            escrow_id.to_string(),
+            Dispute::new(get_current_timestamp(), raised_by.to_string()),
+        );
+        info!("Dispute opened on escrow {} by {}", escrow_id, raised_by);
+        Ok(())
+    }
+
+    /// Records `arbiter_did`'s vote on an open dispute. Once a strict
+    /// majority of the escrow's arbiters has voted the same way, the
+    /// dispute resolves by opening a FROST signing round targeting the
+    /// winning outcome (`Completed` for the seller, `Refunded` for the
+    /// buyer) so settlement still goes through the normal threshold-signed
+    /// path rather than being decided unilaterally.
+    pub async fn cast_arbiter_vote(&self, escrow_id: &str, arbiter_did: &str, favor_seller: bool) -> Result<Option<bool>> {
+        let arbiters = {
+            let contracts = self.contracts.read().await;
+            let contract = contracts
+                .get(escrow_id)
+                .ok_or_else(|| anyhow!("Escrow contract not found: {}", escrow_id))?;
+            if !contract.arbiters.iter().any(|a| a == arbiter_did) {
+                return Err(anyhow!("{} is not an arbiter for escrow {}", arbiter_did, escrow_id));
+            }
+            contract.arbiters.clone()
+        };
+
+        let decision = {
+            let mut disputes = self.disputes.write().await;
+            let dispute = disputes
+                .get_mut(escrow_id)
+                .ok_or_else(|| anyhow!("No open dispute for escrow {}", escrow_id))?;
+            dispute.votes.insert(arbiter_did.to_string(), favor_seller);
+            dispute.majority(&arbiters)
+        };
+
+        if let Some(favor_seller) = decision {
+            self.disputes.write().await.remove(escrow_id);
+            let target_state = if favor_seller { EscrowState::Completed } else { EscrowState::Refunded };
+            self.begin_signing_round(escrow_id, target_state).await?;
+            info!(
+                "Dispute on escrow {} resolved in favor of the {}",
+                escrow_id,
+                if favor_seller { "seller" } else { "buyer" }
+            );
+        }
+
+        Ok(decision)
+    }
+
+    /// Resolves any disputes whose arbiter-vote window has elapsed without
+    /// a majority in the buyer's favor by default, so funds aren't stuck
+    /// forever waiting on arbiters who never vote. Returns the escrow ids
+    /// that were auto-resolved. Intended to be polled periodically.
+    pub async fn check_dispute_timeouts(&self) -> Result<Vec<String>> {
+        let now = get_current_timestamp();
+        let expired: Vec<String> = {
+            let disputes = self.disputes.read().await;
+            disputes
+                .iter()
+                .filter(|(_, dispute)| dispute.is_expired(now))
+                .map(|(escrow_id, _)| escrow_id.clone())
+                .collect()
+        };
+
+        for escrow_id in &expired {
+            self.disputes.write().await.remove(escrow_id);
+            self.begin_signing_round(escrow_id, EscrowState::Refunded).await?;
+            info!("Dispute on escrow {} timed out, defaulting to a refund", escrow_id);
+        }
+
+        Ok(expired)
+    }
+
+    /// Resolves an open dispute on `escrow_id` in favor of the seller
+    /// (`favor_seller`) or the buyer, the same way a winning arbiter
+    /// majority or an expired vote window would — by opening a FROST
+    /// signing round targeting the winning outcome. For an authority
+    /// resolving a dispute outside the normal arbiter vote (e.g. after
+    /// off-chain arbitration).
+    pub async fn resolve_dispute(&self, escrow_id: &str, favor_seller: bool) -> Result<()> {
+        let had_dispute = self.disputes.write().await.remove(escrow_id).is_some();
+        if !had_dispute {
+            return Err(anyhow!("No open dispute for escrow {}", escrow_id));
+        }
+
+        let target_state = if favor_seller { EscrowState::Completed } else { EscrowState::Refunded };
+        self.begin_signing_round(escrow_id, target_state).await?;
+        info!(
+            "Dispute on escrow {} resolved manually in favor of the {}",
+            escrow_id,
+            if favor_seller { "seller" } else { "buyer" }
+        );
+        Ok(())
+    }
+
+    /// Opens a FROST signing round targeting `Refunded` for each contract
+    /// still stuck in `Created`, `Funded`, or `InProgress` past its
+    /// `funding_deadline` that doesn't already have one, so funds aren't
+    /// locked forever when a counterparty never funds or never delivers;
+    /// the refund completes once threshold shares are submitted. Returns
+    /// the escrow ids whose refund round was opened by this call.
+    /// Intended to be polled periodically, alongside `check_dispute_timeouts`.
+    pub async fn check_funding_timeouts(&self) -> Result<Vec<String>> {
+        let now = get_current_timestamp();
+        let candidates: Vec<String> = {
+            let contracts = self.contracts.read().await;
+            contracts
+                .values()
+                .filter(|contract| {
+                    matches!(contract.state, EscrowState::Created | EscrowState::Funded | EscrowState::InProgress)
+                        && now >= contract.funding_deadline
+                })
+                .map(|contract| contract.id.clone())
+                .collect()
+        };
+        let expired: Vec<String> = {
+            let rounds = self.signing_rounds.read().await;
+            candidates
+                .into_iter()
+                .filter(|id| !rounds.get(id).map_or(false, |round| matches!(round.target_state, EscrowState::Refunded)))
+                .collect()
+        };
+
+        for escrow_id in &expired {
+            self.begin_signing_round(escrow_id, EscrowState::Refunded).await?;
+            info!("Escrow {} exceeded its funding/response deadline, defaulting to a refund", escrow_id);
+        }
+
+        Ok(expired)
+    }
+
+    pub async fn get_contract(&self, escrow_id: &str) -> Option<EscrowContract> {
+        let contracts = self.contracts.read().await;
+        contracts.get(escrow_id).cloned()
+    }
+
+    pub async fn update_state(&self, escrow_id: &str, new_state: EscrowState) -> Result<()> {
+        let contract = {
+            let mut contracts = self.contracts.write().await;
+            let contract = contracts
+                .get_mut(escrow_id)
+                .ok_or_else(|| anyhow::anyhow!("Escrow contract not found: {}", escrow_id))?;
+            contract.state = new_state.clone();
+            info!("Updated escrow {} state to {:?}", escrow_id, new_state);
+            contract.clone()
+        };
+
+        if new_state == EscrowState::Refunded {
+            self.backend.refund(&contract).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_contracts_for_did(&self, did: &str) -> Vec<EscrowContract> {
+        let contracts = self.contracts.read().await;
+        contracts
+            .values()
+            .filter(|contract| {
+                contract.buyer_did == did ||
+                contract.seller_did == did ||
+                contract.arbiters.contains(&did.to_string())
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub async fn get_pending_contracts(&self) -> Vec<EscrowContract> {
+        let contracts = self.contracts.read().await;
+        contracts
+            .values()
+            .filter(|contract| {
+                matches!(contract.state, EscrowState::Created | EscrowState::Funded | EscrowState::InProgress)
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub async fn get_completed_contracts(&self) -> Vec<EscrowContract> {
+        let contracts = self.contracts.read().await;
+        contracts
+            .values()
+            .filter(|contract| {
+                matches!(contract.state, EscrowState::Completed | EscrowState::Refunded)
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub async fn get_disputed_contracts(&self) -> Vec<EscrowContract> {
+        let contracts = self.contracts.read().await;
+        contracts
+            .values()
+            .filter(|contract| contract.state == EscrowState::Disputed)
+            .cloned()
+            .collect()
+    }
+
+    pub async fn get_stats(&self) -> EscrowStats {
+        let contracts = self.contracts.read().await;
+
+        let mut stats = EscrowStats {
+            total_contracts: contracts.len(),
+            created: 0,
+            funded: 0,
+            in_progress: 0,
+            completed: 0,
+            disputed: 0,
+            refunded: 0,
+            total_amount: 0,
+        };
+
+        for contract in contracts.values() {
+            match contract.state {
+                EscrowState::Created => stats.created += 1,
+                EscrowState::Funded => {
+                    stats.funded += 1;
+                    stats.total_amount += contract.amount;
+                }
+                EscrowState::InProgress => {
+                    stats.in_progress += 1;
+                    stats.total_amount += contract.amount;
+                }
+                EscrowState::Completed => {
+                    stats.completed += 1;
+                    stats.total_amount += contract.amount;
+                }
+                EscrowState::Disputed => {
+                    stats.disputed += 1;
+                    stats.total_amount += contract.amount;
+                }
+                EscrowState::Refunded => stats.refunded += 1,
+            }
+        }
+
+        stats
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EscrowStats {
+    pub total_contracts: usize,
+    pub created: usize,
+    pub funded: usize,
+    pub in_progress: usize,
+    pub completed: usize,
+    pub disputed: usize,
+    pub refunded: usize,
+    pub total_amount: u64,
+}
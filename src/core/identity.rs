@@ -4,6 +4,45 @@ use ed25519_dalek::{SigningKey, VerifyingKey, Signature, Signer, Verifier};
 use rand::rngs::OsRng;
 use tracing::{debug, info};
 
+/// Canonical bytes an attestation's signature is taken over — shared by
+/// signing and verification so the two can never drift apart.
+pub fn attestation_message(attestation: &ReputationAttestation) -> String {
+    format!(
+        "{}:{}:{}:{}",
+        attestation.attester_did, attestation.target_did, attestation.score, attestation.interaction_type
+    )
+}
+
+/// Canonical bytes a `ServiceMetadata` announcement's signature is taken
+/// over — shared by signing and verification. `reputation_score` isn't
+/// included since it's the announcer's own self-reported snapshot, not
+/// part of the commitment a forged/tampered listing would need to fake.
+pub fn service_message(service: &ServiceMetadata) -> String {
+    format!(
+        "{}:{}:{}:{}:{}:{}:{}",
+        service.id.0, service.provider_did, service.name, service.description, service.endpoint, service.price, service.last_updated
+    )
+}
+
+/// Verifies `signature` over `message` against an arbitrary public key,
+/// rather than the caller's own — used to check a resolved DID's key
+/// instead of assuming the attester and the verifier are the same node.
+pub fn verify_with_public_key(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    use std::convert::TryInto;
+    let Ok(key_bytes): Result<[u8; 32], _> = public_key.to_vec().try_into() else {
+        return false;
+    };
+    let Ok(verifying_key) = VerifyingKey::from_bytes(&key_bytes) else {
+        return false;
+    };
+    let Ok(sig_bytes): Result<[u8; 64], _> = signature.to_vec().try_into() else {
+        return false;
+    };
+    let signature = Signature::from_bytes(&sig_bytes);
+    verifying_key.verify(message, &signature).is_ok()
+}
+
+#[derive(Clone)]
 pub struct DIDManager {
     pub secret_key: Vec<u8>, // Store only the secret key bytes
     pub keypair: SigningKey,
@@ -64,20 +103,8 @@ impl DIDManager {
         }
     }
 
-    pub fn verify_attestation(&self, attestation: &ReputationAttestation) -> bool {
-        let message = format!("{}:{}:{}:{}", 
-            attestation.attester_did, 
-            attestation.target_did, 
-            attestation.score, 
-            attestation.interaction_type
-        );
-        
-        // For now, we'll verify against our own public key
-        // In a real implementation, you'd need to resolve the attester's DID
-        self.verify_signature(
-            &attestation.signature,
-            message.as_bytes()
-        ).unwrap_or(false)
+    pub fn verify_own_attestation(&self, attestation: &ReputationAttestation) -> bool {
+        verify_with_public_key(&self.get_public_key(), attestation_message(attestation).as_bytes(), &attestation.signature)
     }
 
     pub fn sign_escrow_contract(&self, escrow_id: &str, state: &EscrowState) -> Vec<u8> {
@@ -87,7 +114,7 @@ impl DIDManager {
 
     pub fn verify_escrow_signature(&self, escrow_id: &str, state: &EscrowState, signature: &[u8], public_key: &[u8]) -> bool {
         let message = format!("{}:{}", escrow_id, serde_json::to_string(state).unwrap());
-        self.verify_signature(signature, message.as_bytes()).unwrap_or(false)
+        verify_with_public_key(public_key, message.as_bytes(), signature)
     }
 
     pub fn export_private_key(&self) -> Vec<u8> {
@@ -0,0 +1,157 @@
+//! Crate-wide metrics registry for `DHT` operations. `DHTStats` (in
+//! `dht.rs`) only ever exposed flat entry counts, which isn't enough to
+//! diagnose a live node — this module adds counters and latency
+//! histograms instead, in the same "collection threaded through the
+//! component, snapshotted on demand" shape `rpc.rs`'s `RpcContext`
+//! already uses for the control surface.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+/// How many latency samples a single histogram keeps before dropping the
+/// oldest — enough to compute stable quantiles on a long-running node
+/// without the sample set growing without bound.
+const MAX_SAMPLES: usize = 1024;
+
+#[derive(Debug, Default)]
+struct Histogram {
+    /// Milliseconds (or, for `lookup_hops`, raw hop counts), oldest-first.
+    /// A `VecDeque` so dropping the oldest sample once full is O(1)
+    /// instead of shifting the whole buffer.
+    samples: VecDeque<f64>,
+}
+
+impl Histogram {
+    fn record(&mut self, value: f64) {
+        if self.samples.len() >= MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+    }
+
+    fn quantiles(&self) -> Quantiles {
+        if self.samples.is_empty() {
+            return Quantiles::default();
+        }
+        let mut sorted: Vec<f64> = self.samples.iter().copied().collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let pick = |q: f64| sorted[(((sorted.len() - 1) as f64) * q).round() as usize];
+        Quantiles { p50: pick(0.50), p90: pick(0.90), p99: pick(0.99), count: sorted.len() as u64 }
+    }
+}
+
+/// p50/p90/p99 of a `Histogram`'s current samples, plus how many went in.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Quantiles {
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub count: u64,
+}
+
+/// A point-in-time read of the registry — what `DHT::get_metrics`
+/// returns and what `DuxNetNode`'s event loop logs periodically.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    pub store_total: u64,
+    pub get_hits: u64,
+    pub get_misses: u64,
+    pub evictions_total: u64,
+    /// Hops `iterative_lookup` took to converge, across all keys.
+    pub lookup_hops: Quantiles,
+    /// Latency of `store`/`get` calls, keyed by the key's prefix
+    /// (`service`, `reputation`, `escrow`, `did`, ...) so a slow prefix
+    /// doesn't get averaged away by the rest.
+    pub prefix_timings: HashMap<String, Quantiles>,
+}
+
+/// Crate-wide metrics registry, threaded through `DHT` (and, via it,
+/// `DuxNetNode`). Cheap to clone — every field is an `Arc` handle, same
+/// pattern as `ReputationSystem`/`TaskEngine`.
+#[derive(Clone)]
+pub struct Metrics {
+    store_total: Arc<AtomicU64>,
+    get_hits: Arc<AtomicU64>,
+    get_misses: Arc<AtomicU64>,
+    evictions_total: Arc<AtomicU64>,
+    lookup_hops: Arc<RwLock<Histogram>>,
+    prefix_timings: Arc<RwLock<HashMap<String, Histogram>>>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Metrics {
+            store_total: Arc::new(AtomicU64::new(0)),
+            get_hits: Arc::new(AtomicU64::new(0)),
+            get_misses: Arc::new(AtomicU64::new(0)),
+            evictions_total: Arc::new(AtomicU64::new(0)),
+            lookup_hops: Arc::new(RwLock::new(Histogram::default())),
+            prefix_timings: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Prefix a `DHT` key is bucketed under for `prefix_timings` — the
+    /// part before the first `:`, e.g. `service:abc` -> `service`.
+    fn prefix_of(key: &str) -> &str {
+        key.split(':').next().unwrap_or(key)
+    }
+
+    pub async fn record_store(&self, key: &str, elapsed: Duration) {
+        self.store_total.fetch_add(1, Ordering::Relaxed);
+        self.record_prefix_timing(key, elapsed).await;
+    }
+
+    pub async fn record_get(&self, key: &str, elapsed: Duration, hit: bool) {
+        if hit {
+            self.get_hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.get_misses.fetch_add(1, Ordering::Relaxed);
+        }
+        self.record_prefix_timing(key, elapsed).await;
+    }
+
+    async fn record_prefix_timing(&self, key: &str, elapsed: Duration) {
+        let mut timings = self.prefix_timings.write().await;
+        timings
+            .entry(Self::prefix_of(key).to_string())
+            .or_insert_with(Histogram::default)
+            .record(elapsed.as_secs_f64() * 1000.0);
+    }
+
+    pub async fn record_lookup_hops(&self, hops: u64) {
+        self.lookup_hops.write().await.record(hops as f64);
+    }
+
+    pub fn record_evictions(&self, count: u64) {
+        self.evictions_total.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub async fn snapshot(&self) -> MetricsSnapshot {
+        let prefix_timings = self
+            .prefix_timings
+            .read()
+            .await
+            .iter()
+            .map(|(prefix, histogram)| (prefix.clone(), histogram.quantiles()))
+            .collect();
+
+        MetricsSnapshot {
+            store_total: self.store_total.load(Ordering::Relaxed),
+            get_hits: self.get_hits.load(Ordering::Relaxed),
+            get_misses: self.get_misses.load(Ordering::Relaxed),
+            evictions_total: self.evictions_total.load(Ordering::Relaxed),
+            lookup_hops: self.lookup_hops.read().await.quantiles(),
+            prefix_timings,
+        }
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,8 +1,13 @@
 pub mod data_structures;
 pub mod dht;
+pub mod dht_store;
+pub mod did_resolver;
 pub mod identity;
+pub mod metrics;
 pub mod reputation;
 pub mod escrow;
+pub mod rpc;
+pub mod swap;
 pub mod tasks;
 
 use anyhow::Result;
@@ -11,59 +16,117 @@ use tokio::sync::RwLock;
 use tracing::{info, error};
 
 use data_structures::*;
-use dht::DHT;
+use did_resolver::DhtDidResolver;
+use dht::{DHT, NodeMode};
 use identity::DIDManager;
-use reputation::ReputationSystem;
+use reputation::{ReputationSystem, ServicePolicy};
 use escrow::EscrowManager;
+use swap::SwapManager;
 use tasks::TaskEngine;
-use crate::network::P2PNetwork;
+use crate::network::{Libp2pNetwork, Network, TOPIC_ESCROW};
+use crate::wallet::oracle::{PriceOracle, StaticOracle};
+use crate::wallet::rate::{HttpPriceProvider, RateCache};
 use crate::wallet::Wallet;
 
 pub struct DuxNetNode {
     pub node_id: NodeId,
     pub did_manager: DIDManager,
-    pub dht: DHT,
+    pub dht: Arc<DHT>,
     pub reputation_system: ReputationSystem,
-    pub escrow_manager: EscrowManager,
+    /// Minimum-reputation / allow-deny gate applied to services this node
+    /// announces (`register_service`) and surfaces to callers
+    /// (`find_services`); defaults to accepting everyone.
+    pub service_policy: ServicePolicy,
+    pub escrow_manager: Arc<EscrowManager>,
+    pub swap_manager: SwapManager,
+    pub rate_cache: RateCache,
+    pub price_oracle: Arc<dyn PriceOracle>,
     pub task_engine: TaskEngine,
-    pub network: Arc<P2PNetwork>,
+    pub network: Arc<dyn Network>,
     pub wallet: Arc<RwLock<crate::wallet::Wallet>>,
     pub is_running: Arc<RwLock<bool>>,
+    /// TCP port for the control RPC (`rpc` module); the Unix-socket IPC
+    /// endpoint is derived from `node_id` instead, since it doesn't need
+    /// a reserved number.
+    pub rpc_port: u16,
 }
 
 impl DuxNetNode {
     pub async fn new(port: u16) -> Result<Self> {
+        Self::new_with_mode(port, NodeMode::Full).await
+    }
+
+    /// Like `new`, but runs as a light client: it leans on full nodes for
+    /// data it doesn't keep a local copy of instead of maintaining one
+    /// itself. See `core::dht::DHT::light`.
+    pub async fn new_light(port: u16) -> Result<Self> {
+        Self::new_with_mode(port, NodeMode::Light).await
+    }
+
+    async fn new_with_mode(port: u16, mode: NodeMode) -> Result<Self> {
         let node_id = NodeId(uuid::Uuid::new_v4().to_string());
         let endpoints = vec![format!("tcp://127.0.0.1:{}", port)];
-        
+
         let did_manager = DIDManager::new(endpoints);
-        let dht = DHT::new(node_id.clone());
-        let reputation_system = ReputationSystem::new();
-        let escrow_manager = EscrowManager::new();
+        let dht = Arc::new(match mode {
+            NodeMode::Full => DHT::new(node_id.clone()),
+            NodeMode::Light => DHT::light(node_id.clone()),
+        });
+        let reputation_system = ReputationSystem::new(Arc::new(DhtDidResolver::new(dht.clone())));
+        let escrow_manager = Arc::new(EscrowManager::new());
+        let swap_manager = SwapManager::new(escrow_manager.clone());
+        let rate_cache = RateCache::new(Arc::new(HttpPriceProvider::new("https://prices.duxnet.network")));
+        let price_oracle: Arc<dyn PriceOracle> = Arc::new(StaticOracle::default());
         let task_engine = TaskEngine::new();
-        let network = Arc::new(P2PNetwork::new(port).await?);
+        let network: Arc<dyn Network> = Arc::new(Libp2pNetwork::new(port, &did_manager, dht.clone()).await?);
         let wallet = Arc::new(RwLock::new(crate::wallet::Wallet::new(did_manager.did.id.clone())?));
         let is_running = Arc::new(RwLock::new(false));
-        
+        let rpc_port = port + 1000;
+
+        // Announce our own DID document so peers can resolve our key and
+        // verify attestations/messages we sign, instead of only ever
+        // being able to verify their own.
+        dht.announce_did(&did_manager.did).await?;
+
         Ok(DuxNetNode {
             node_id,
             did_manager,
             dht,
             reputation_system,
+            service_policy: ServicePolicy::default(),
             escrow_manager,
+            swap_manager,
+            rate_cache,
+            price_oracle,
             task_engine,
             network,
             wallet,
             is_running,
+            rpc_port,
         })
     }
 
     pub async fn start(&mut self) -> Result<()> {
         info!("Starting DuxNet node: {}", self.node_id.0);
-        
+
+        // Start the JSON-RPC control surface (TCP + Unix-socket IPC) so
+        // CLIs/dashboards can drive this node without linking against
+        // the crate directly.
+        let rpc_ctx = rpc::RpcContext {
+            dht: self.dht.clone(),
+            escrow_manager: self.escrow_manager.clone(),
+            reputation_system: self.reputation_system.clone(),
+            task_engine: self.task_engine.clone(),
+            did: self.did_manager.did.clone(),
+            did_manager: Arc::new(self.did_manager.clone()),
+            service_policy: self.service_policy.clone(),
+        };
+        let socket_path = std::env::temp_dir().join(format!("duxnet-rpc-{}.sock", self.node_id.0));
+        rpc::start(rpc_ctx, self.rpc_port, socket_path);
+
         // Start the P2P network
         self.network.start().await?;
-        
+
         // Mark as running
         {
             let mut running = self.is_running.write().await;
@@ -77,6 +140,17 @@ impl DuxNetNode {
     }
 
     async fn event_loop(&self) -> Result<()> {
+        let mut last_metrics_emit = std::time::Instant::now();
+        const METRICS_EMIT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+        // Simulates the chain's block source: ticks the wallet's mempool
+        // forward on a fixed cadence so `receive_funds`-queued transactions
+        // actually accrue confirmations and get credited instead of sitting
+        // in `Pending` forever (see `Wallet::advance_mempool`).
+        let mut last_mempool_tick = std::time::Instant::now();
+        const MEMPOOL_TICK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+        let mut block_height: u64 = 0;
+
         loop {
             // Check if we should stop
             {
@@ -95,7 +169,43 @@ impl DuxNetNode {
             if let Err(e) = self.task_engine.process_pending_tasks().await {
                 error!("Task processing error: {}", e);
             }
-            
+
+            // Auto-resolve disputes whose arbiter vote timed out
+            if let Err(e) = self.escrow_manager.check_dispute_timeouts().await {
+                error!("Dispute timeout processing error: {}", e);
+            }
+
+            // Auto-refund escrows that never finished funding/delivery
+            if let Err(e) = self.escrow_manager.check_funding_timeouts().await {
+                error!("Escrow funding timeout processing error: {}", e);
+            }
+
+            // Auto-refund atomic swap legs whose counterparty never locked
+            // the mirror contract (or whose initiator never redeemed) in time
+            if let Err(e) = self.swap_manager.check_timeouts().await {
+                error!("Swap timeout processing error: {}", e);
+            }
+
+            // Periodically surface DHT health so operators can track it
+            // over time without polling `get_metrics` themselves
+            if last_metrics_emit.elapsed() >= METRICS_EMIT_INTERVAL {
+                let metrics = self.dht.get_metrics().await;
+                info!("DHT metrics snapshot: {:?}", metrics);
+                last_metrics_emit = std::time::Instant::now();
+            }
+
+            // Advance the mempool so received funds actually settle into
+            // `balances` once they cross their currency's confirmation
+            // threshold, instead of sitting queued forever.
+            if last_mempool_tick.elapsed() >= MEMPOOL_TICK_INTERVAL {
+                block_height += 1;
+                let mut wallet = self.wallet.write().await;
+                if let Err(e) = wallet.advance_mempool(block_height) {
+                    error!("Mempool advance error: {}", e);
+                }
+                last_mempool_tick = std::time::Instant::now();
+            }
+
             // Sleep briefly to prevent busy waiting
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
         }
@@ -118,31 +228,56 @@ impl DuxNetNode {
         Ok(())
     }
 
+    /// Replaces the reputation/allow-deny gate applied to future
+    /// `register_service`/`find_services` calls.
+    pub fn set_service_policy(&mut self, policy: ServicePolicy) {
+        self.service_policy = policy;
+    }
+
     // Service management
-    pub async fn register_service(&self, name: String, description: String, 
+    pub async fn register_service(&self, name: String, description: String,
                                   price: u64) -> Result<ServiceId> {
+        let own_did = &self.did_manager.did.id;
+        let reputation = self.reputation_system.get_reputation(own_did).await;
+        if !self.service_policy.allows(own_did, reputation) {
+            return Err(anyhow::anyhow!(
+                "service policy denies registering services for {}",
+                own_did
+            ));
+        }
+
         let service_id = ServiceId(uuid::Uuid::new_v4().to_string());
-        let service = ServiceMetadata {
+        let mut service = ServiceMetadata {
             id: service_id.clone(),
-            provider_did: self.did_manager.did.id.clone(),
+            provider_did: own_did.clone(),
             name,
             description,
             endpoint: self.did_manager.did.endpoints[0].clone(),
             price,
-            reputation_score: self.reputation_system.get_reputation(&self.did_manager.did.id).await,
+            reputation_score: reputation,
             last_updated: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs(),
+            signature: Vec::new(),
         };
-        
+        service.signature = self.did_manager.sign_message(identity::service_message(&service).as_bytes());
+
         self.dht.announce_service(&service).await?;
+        self.network.announce_service(&service).await?;
         info!("Registered service: {}", service_id.0);
         Ok(service_id)
     }
 
     pub async fn find_services(&self, query: &str) -> Vec<ServiceMetadata> {
-        self.dht.find_services(query).await
+        let mut services = Vec::new();
+        for service in self.dht.find_services(query).await {
+            let reputation = self.reputation_system.get_reputation(&service.provider_did).await;
+            if self.service_policy.allows(&service.provider_did, reputation) {
+                services.push(service);
+            }
+        }
+        services
     }
 
     // Escrow management
@@ -157,13 +292,72 @@ impl DuxNetNode {
             self.did_manager.did.id.clone(),
             seller_did,
             arbiters,
-            amount
+            amount,
+            Some(service_id.clone()),
         ).await?;
         
         info!("Created escrow: {}", escrow_id);
         Ok(escrow_id)
     }
 
+    /// Same as `create_escrow_for_service`, but `usd_amount` denominates
+    /// the locked value in USD instead of `currency`'s native units; it's
+    /// resolved via the live rate at lock time rather than the caller
+    /// having to track a rate itself.
+    pub async fn create_escrow_for_service_usd(
+        &self,
+        service_id: &ServiceId,
+        seller_did: String,
+        usd_amount: rust_decimal::Decimal,
+        currency: crate::wallet::Currency,
+    ) -> Result<String> {
+        let rate = self.rate_cache.get_rate(currency).await?;
+        let amount = crate::wallet::rate::from_usd(currency, usd_amount, &rate)?;
+        self.create_escrow_for_service(service_id, seller_did, amount).await
+    }
+
+    /// Raises a dispute on `escrow_id` as the local node and broadcasts it
+    /// so the other party and arbiters see it without polling.
+    pub async fn open_dispute(&self, escrow_id: &str) -> Result<()> {
+        let raised_by = self.did_manager.did.id.clone();
+        self.escrow_manager.open_dispute(escrow_id, &raised_by).await?;
+        self.network
+            .publish_message(TOPIC_ESCROW, &NetworkMessage::DisputeOpened(escrow_id.to_string(), raised_by))
+            .await?;
+        Ok(())
+    }
+
+    /// Locks `proofs` to `escrow_id`'s P2PK condition and broadcasts them
+    /// so the seller doesn't have to poll to learn the buyer has funded a
+    /// Cashu-settled escrow.
+    pub async fn lock_ecash_proofs(&self, escrow_id: &str, proofs: Vec<CashuProof>) -> Result<()> {
+        self.escrow_manager.lock_proofs(escrow_id, proofs.clone()).await?;
+        self.network
+            .publish_message(TOPIC_ESCROW, &NetworkMessage::EcashLockedProofs(escrow_id.to_string(), proofs))
+            .await?;
+        Ok(())
+    }
+
+    /// Contributes `signer_did`'s FROST signature share toward `escrow_id`'s
+    /// open signing round. Once the threshold is met the aggregated
+    /// signature is broadcast as the Cashu unlock witness, so the other
+    /// party can redeem the locked proofs without re-deriving it.
+    pub async fn submit_escrow_signature_share(&self, escrow_id: &str, signer_did: &str) -> Result<bool> {
+        let ready = self.escrow_manager.submit_signature_share(escrow_id, signer_did).await?;
+        if ready {
+            if let Some(contract) = self.escrow_manager.get_contract(escrow_id).await {
+                if !contract.locked_proofs.is_empty() {
+                    if let Some(witness) = contract.settlement_signature.clone() {
+                        self.network
+                            .publish_message(TOPIC_ESCROW, &NetworkMessage::EcashUnlockWitness(escrow_id.to_string(), witness))
+                            .await?;
+                    }
+                }
+            }
+        }
+        Ok(ready)
+    }
+
     // Task management
     pub async fn submit_task(&self, service_id: ServiceId, payload: Vec<u8>, 
                              requirements: TaskRequirements) -> Result<TaskId> {
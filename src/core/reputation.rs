@@ -1,30 +1,53 @@
 use crate::core::data_structures::*;
-use anyhow::Result;
+use crate::core::did_resolver::DidResolver;
+use crate::core::identity::{attestation_message, verify_with_public_key};
+use anyhow::{anyhow, Result};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info};
 
+#[derive(Clone)]
 pub struct ReputationSystem {
     pub attestations: Arc<RwLock<HashMap<String, Vec<ReputationAttestation>>>>,
     pub scores: Arc<RwLock<HashMap<String, f64>>>,
+    did_resolver: Arc<dyn DidResolver>,
 }
 
 impl ReputationSystem {
-    pub fn new() -> Self {
+    pub fn new(did_resolver: Arc<dyn DidResolver>) -> Self {
         ReputationSystem {
             attestations: Arc::new(RwLock::new(HashMap::new())),
             scores: Arc::new(RwLock::new(HashMap::new())),
+            did_resolver,
         }
     }
 
     pub async fn add_attestation(&self, attestation: ReputationAttestation) -> Result<()> {
+        let attester = self
+            .did_resolver
+            .resolve(&attestation.attester_did)
+            .await
+            .ok_or_else(|| anyhow!("cannot resolve attester DID: {}", attestation.attester_did))?;
+
+        if !verify_with_public_key(
+            &attester.public_key,
+            attestation_message(&attestation).as_bytes(),
+            &attestation.signature,
+        ) {
+            return Err(anyhow!(
+                "attestation signature does not match attester {}'s resolved key",
+                attestation.attester_did
+            ));
+        }
+
         let mut attestations = self.attestations.write().await;
         attestations
             .entry(attestation.target_did.clone())
             .or_insert_with(Vec::new)
             .push(attestation.clone());
-        
+
+        drop(attestations);
         self.recalculate_score(&attestation.target_did).await;
         debug!("Added reputation attestation for: {}", attestation.target_did);
         Ok(())
@@ -121,4 +144,38 @@ pub struct ReputationStats {
     pub total_nodes: usize,
     pub total_attestations: usize,
     pub average_score: f64,
-} 
\ No newline at end of file
+}
+
+/// Gates which providers `DuxNetNode::register_service`/`find_services`
+/// will announce or surface — the "refuse-service-transactions" model
+/// applied to the service marketplace instead of just transactions. An
+/// explicit `denied_providers` entry always wins over `allowed_providers`,
+/// which in turn always wins over `min_reputation`, so an operator can
+/// ban a specific bad actor without having to also raise the reputation
+/// floor for everyone else.
+#[derive(Debug, Clone)]
+pub struct ServicePolicy {
+    pub min_reputation: f64,
+    pub allowed_providers: Option<Vec<String>>,
+    pub denied_providers: Option<Vec<String>>,
+}
+
+impl Default for ServicePolicy {
+    fn default() -> Self {
+        ServicePolicy { min_reputation: 0.0, allowed_providers: None, denied_providers: None }
+    }
+}
+
+impl ServicePolicy {
+    pub fn allows(&self, provider_did: &str, reputation: f64) -> bool {
+        if let Some(denied) = &self.denied_providers {
+            if denied.iter().any(|did| did == provider_did) {
+                return false;
+            }
+        }
+        if let Some(allowed) = &self.allowed_providers {
+            return allowed.iter().any(|did| did == provider_did);
+        }
+        reputation >= self.min_reputation
+    }
+}
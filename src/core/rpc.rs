@@ -0,0 +1,334 @@
+//! A JSON-RPC 2.0 control surface for a running `DuxNetNode`, served over
+//! both a plain TCP listener and a local Unix-domain socket — the dual
+//! HTTP+IPC transport model Parity ships its own control RPC over, so a
+//! CLI or dashboard on the same host can reach a node over the socket
+//! without opening a network port, while remote tooling still has TCP.
+//! Unlike `api::rpc`, which speaks JSON-RPC over the wallet's HTTP API,
+//! this one drives the node's P2P/service-marketplace surface directly
+//! and isn't routed through axum — each connection is a line-delimited
+//! JSON-RPC request/response session.
+//!
+//! Started from `DuxNetNode::start`, which builds an `RpcContext` from
+//! its own fields and hands it to `start`.
+
+use crate::core::data_structures::*;
+use crate::core::escrow::EscrowManager;
+use crate::core::dht::DHT;
+use crate::core::identity::{self, DIDManager};
+use crate::core::reputation::{ReputationSystem, ServicePolicy};
+use crate::core::tasks::TaskEngine;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tracing::{error, info, warn};
+
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    #[allow(dead_code)]
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Value,
+    #[serde(default)]
+    id: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcResponse {
+    jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<JsonRpcError>,
+    id: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+impl JsonRpcResponse {
+    fn ok(id: Value, result: Value) -> Self {
+        JsonRpcResponse { jsonrpc: "2.0", result: Some(result), error: None, id }
+    }
+
+    fn err(id: Value, code: i64, message: impl Into<String>) -> Self {
+        JsonRpcResponse {
+            jsonrpc: "2.0",
+            result: None,
+            error: Some(JsonRpcError { code, message: message.into() }),
+            id,
+        }
+    }
+}
+
+/// The slice of a `DuxNetNode` the control RPC needs, cloned out at
+/// `start` time so each connection handler can own a copy instead
+/// of fighting the node's `&mut self` lifecycle. Every field is cheap to
+/// clone — `dht`/`escrow_manager` are already `Arc`, and `ReputationSystem`/
+/// `TaskEngine` are themselves just `Arc<RwLock<_>>` handles.
+#[derive(Clone)]
+pub struct RpcContext {
+    pub dht: Arc<DHT>,
+    pub escrow_manager: Arc<EscrowManager>,
+    pub reputation_system: ReputationSystem,
+    pub task_engine: TaskEngine,
+    pub did: DID,
+    /// Signing half of `did`, needed to produce `ServiceMetadata::signature`
+    /// for `duxnet_registerService` — `did` alone (the public DID document)
+    /// isn't enough to sign an announcement.
+    pub did_manager: Arc<DIDManager>,
+    /// Same gate `DuxNetNode::register_service`/`find_services` apply,
+    /// mirrored here since this control surface talks to the DHT directly
+    /// instead of going through the node's methods.
+    pub service_policy: ServicePolicy,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegisterServiceParams {
+    name: String,
+    description: String,
+    price: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct FindServicesParams {
+    query: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetReputationParams {
+    did: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateEscrowParams {
+    service_id: String,
+    seller_did: String,
+    amount: u64,
+    arbiters: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubmitTaskParams {
+    service_id: String,
+    payload: Vec<u8>,
+    requirements: TaskRequirements,
+}
+
+#[derive(Debug, Deserialize)]
+struct AddReputationAttestationParams {
+    attestation: ReputationAttestation,
+}
+
+async fn dispatch(ctx: &RpcContext, method: &str, params: Value) -> Result<Value, (i64, String)> {
+    match method {
+        "duxnet_registerService" => {
+            let p: RegisterServiceParams =
+                serde_json::from_value(params).map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+            let reputation = ctx.reputation_system.get_reputation(&ctx.did.id).await;
+            if !ctx.service_policy.allows(&ctx.did.id, reputation) {
+                return Err((INVALID_REQUEST, format!("service policy denies registering services for {}", ctx.did.id)));
+            }
+            let service_id = ServiceId(uuid::Uuid::new_v4().to_string());
+            let mut service = ServiceMetadata {
+                id: service_id.clone(),
+                provider_did: ctx.did.id.clone(),
+                name: p.name,
+                description: p.description,
+                endpoint: ctx.did.endpoints[0].clone(),
+                price: p.price,
+                reputation_score: reputation,
+                last_updated: get_current_timestamp(),
+                signature: Vec::new(),
+            };
+            service.signature = ctx.did_manager.sign_message(identity::service_message(&service).as_bytes());
+            ctx.dht.announce_service(&service).await.map_err(|e| (INTERNAL_ERROR, e.to_string()))?;
+            Ok(serde_json::json!({ "service_id": service_id.0 }))
+        }
+        "duxnet_findServices" => {
+            let p: FindServicesParams =
+                serde_json::from_value(params).map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+            let mut services = Vec::new();
+            for service in ctx.dht.find_services(&p.query).await {
+                let reputation = ctx.reputation_system.get_reputation(&service.provider_did).await;
+                if ctx.service_policy.allows(&service.provider_did, reputation) {
+                    services.push(service);
+                }
+            }
+            Ok(serde_json::json!({ "services": services }))
+        }
+        "duxnet_getReputation" => {
+            let p: GetReputationParams =
+                serde_json::from_value(params).map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+            let reputation = ctx.reputation_system.get_reputation(&p.did).await;
+            Ok(serde_json::json!({ "did": p.did, "reputation": reputation }))
+        }
+        "duxnet_getStats" => {
+            let stats = ctx.dht.get_stats().await;
+            serde_json::to_value(stats).map_err(|e| (INTERNAL_ERROR, e.to_string()))
+        }
+        "duxnet_getMetrics" => {
+            let metrics = ctx.dht.get_metrics().await;
+            serde_json::to_value(metrics).map_err(|e| (INTERNAL_ERROR, e.to_string()))
+        }
+        "duxnet_createEscrow" => {
+            let p: CreateEscrowParams =
+                serde_json::from_value(params).map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+            let escrow_id = ctx
+                .escrow_manager
+                .create_escrow(
+                    ctx.did.id.clone(),
+                    p.seller_did,
+                    p.arbiters,
+                    p.amount,
+                    Some(ServiceId(p.service_id)),
+                )
+                .await
+                .map_err(|e| (INTERNAL_ERROR, e.to_string()))?;
+            Ok(serde_json::json!({ "escrow_id": escrow_id }))
+        }
+        "duxnet_submitTask" => {
+            let p: SubmitTaskParams =
+                serde_json::from_value(params).map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+            let task_id = TaskId(uuid::Uuid::new_v4().to_string());
+            let task = Task {
+                id: task_id.clone(),
+                escrow_id: "".to_string(),
+                service_id: ServiceId(p.service_id),
+                payload: p.payload,
+                requirements: p.requirements,
+                created_at: get_current_timestamp(),
+            };
+            ctx.task_engine.submit_task(task).await.map_err(|e| (INTERNAL_ERROR, e.to_string()))?;
+            Ok(serde_json::json!({ "task_id": task_id.0 }))
+        }
+        "duxnet_addReputationAttestation" => {
+            let p: AddReputationAttestationParams =
+                serde_json::from_value(params).map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+            ctx.reputation_system
+                .add_attestation(p.attestation)
+                .await
+                .map_err(|e| (INTERNAL_ERROR, e.to_string()))?;
+            Ok(Value::Null)
+        }
+        _ => Err((METHOD_NOT_FOUND, format!("unknown method: {}", method))),
+    }
+}
+
+async fn handle_line(ctx: &RpcContext, line: &str) -> JsonRpcResponse {
+    let call: Value = match serde_json::from_str(line) {
+        Ok(call) => call,
+        Err(e) => return JsonRpcResponse::err(Value::Null, INVALID_REQUEST, e.to_string()),
+    };
+    let request: JsonRpcRequest = match serde_json::from_value(call) {
+        Ok(request) => request,
+        Err(e) => return JsonRpcResponse::err(Value::Null, INVALID_REQUEST, e.to_string()),
+    };
+
+    match dispatch(ctx, &request.method, request.params).await {
+        Ok(result) => JsonRpcResponse::ok(request.id, result),
+        Err((code, message)) => JsonRpcResponse::err(request.id, code, message),
+    }
+}
+
+/// Serves one connection: each line is a complete JSON-RPC request, and
+/// each response is written back newline-terminated, so a client can
+/// pipeline multiple calls over a single long-lived socket.
+async fn serve_connection<S>(ctx: RpcContext, stream: S)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+
+    loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(e) => {
+                warn!("control RPC connection read error: {}", e);
+                break;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = handle_line(&ctx, &line).await;
+        let Ok(mut serialized) = serde_json::to_string(&response) else {
+            break;
+        };
+        serialized.push('\n');
+        if writer.write_all(serialized.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+async fn serve_tcp(ctx: RpcContext, port: u16) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port)).await?;
+    info!("Control RPC listening on tcp://127.0.0.1:{}", port);
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            serve_connection(ctx, stream).await;
+        });
+    }
+}
+
+#[cfg(unix)]
+async fn serve_ipc(ctx: RpcContext, socket_path: PathBuf) -> Result<()> {
+    // Remove a stale socket left behind by an unclean shutdown; a fresh
+    // bind otherwise fails with "address already in use".
+    let _ = std::fs::remove_file(&socket_path);
+    let listener = tokio::net::UnixListener::bind(&socket_path)?;
+    info!("Control RPC listening on ipc://{}", socket_path.display());
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            serve_connection(ctx, stream).await;
+        });
+    }
+}
+
+#[cfg(not(unix))]
+async fn serve_ipc(_ctx: RpcContext, socket_path: PathBuf) -> Result<()> {
+    // Named-pipe IPC for non-Unix targets isn't implemented yet; fail
+    // loudly in logs rather than silently serving TCP only.
+    warn!(
+        "Control RPC IPC endpoint ({}) skipped: named-pipe transport isn't implemented on this platform",
+        socket_path.display()
+    );
+    Ok(())
+}
+
+/// Spawns both transports and returns immediately; each runs until its
+/// listener errors; a failure is logged rather than torn down, since the
+/// other transport can keep serving control requests on its own.
+pub fn start(ctx: RpcContext, tcp_port: u16, socket_path: PathBuf) {
+    let tcp_ctx = ctx.clone();
+    tokio::spawn(async move {
+        if let Err(e) = serve_tcp(tcp_ctx, tcp_port).await {
+            error!("Control RPC TCP listener error: {}", e);
+        }
+    });
+
+    tokio::spawn(async move {
+        if let Err(e) = serve_ipc(ctx, socket_path).await {
+            error!("Control RPC IPC listener error: {}", e);
+        }
+    });
+}
@@ -0,0 +1,316 @@
+//! Trustless cross-currency atomic swaps between two DuxNet peers, built
+//! as a hash-time-locked pair of escrow contracts rather than a custodian.
+//!
+//! The initiator picks a random preimage `s`, locks the "from" currency in
+//! an escrow redeemable by revealing `s` before `timeout_initiator`, and
+//! the counterparty mirrors it with the "to" currency under a strictly
+//! shorter `timeout_counterparty` so the initiator can never redeem both
+//! legs and vanish before the counterparty can react.
+
+use crate::core::escrow::EscrowManager;
+use crate::wallet::Currency;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+/// The mirror leg's timeout must be at least this much shorter than the
+/// initiator's, so the counterparty always has time left to react once
+/// the preimage becomes public.
+const TIMEOUT_MARGIN_SECS: u64 = 1800; // 30 minutes
+const INITIATOR_TIMEOUT_SECS: u64 = 3600; // 1 hour
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SwapState {
+    Created,
+    Locked,
+    Redeemed,
+    Refunded,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Swap {
+    pub id: String,
+    pub initiator_did: String,
+    pub counterparty_did: String,
+    pub from_currency: Currency,
+    pub from_amount: u64,
+    pub to_currency: Currency,
+    pub to_amount: u64,
+    #[serde(with = "hex_bytes")]
+    pub hash: [u8; 32],
+    pub preimage: Option<String>,
+    /// Escrow holding the initiator's `from_currency` leg.
+    pub from_escrow_id: String,
+    /// Escrow holding the counterparty's `to_currency` leg, set once they accept.
+    pub to_escrow_id: Option<String>,
+    pub timeout_initiator: u64,
+    pub timeout_counterparty: Option<u64>,
+    pub state: SwapState,
+    pub created_at: u64,
+}
+
+/// The subset of `Swap` safe to hand back over the API. Notably omits
+/// `preimage`: it must stay known only to the initiator until the
+/// redeem claim that makes it public is itself published, so serializing
+/// it here would let the counterparty read `s` off the status endpoint
+/// and redeem the mirror leg before the initiator ever reveals it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapStatus {
+    pub id: String,
+    pub initiator_did: String,
+    pub counterparty_did: String,
+    pub from_currency: Currency,
+    pub from_amount: u64,
+    pub to_currency: Currency,
+    pub to_amount: u64,
+    #[serde(with = "hex_bytes")]
+    pub hash: [u8; 32],
+    pub from_escrow_id: String,
+    pub to_escrow_id: Option<String>,
+    pub timeout_initiator: u64,
+    pub timeout_counterparty: Option<u64>,
+    pub state: SwapState,
+    pub created_at: u64,
+}
+
+impl From<&Swap> for SwapStatus {
+    fn from(swap: &Swap) -> Self {
+        SwapStatus {
+            id: swap.id.clone(),
+            initiator_did: swap.initiator_did.clone(),
+            counterparty_did: swap.counterparty_did.clone(),
+            from_currency: swap.from_currency,
+            from_amount: swap.from_amount,
+            to_currency: swap.to_currency,
+            to_amount: swap.to_amount,
+            hash: swap.hash,
+            from_escrow_id: swap.from_escrow_id.clone(),
+            to_escrow_id: swap.to_escrow_id.clone(),
+            timeout_initiator: swap.timeout_initiator,
+            timeout_counterparty: swap.timeout_counterparty,
+            state: swap.state,
+            created_at: swap.created_at,
+        }
+    }
+}
+
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
+        bytes.try_into().map_err(|_| serde::de::Error::custom("expected 32 bytes"))
+    }
+}
+
+pub struct SwapManager {
+    swaps: Arc<RwLock<HashMap<String, Swap>>>,
+    escrow_manager: Arc<EscrowManager>,
+}
+
+impl SwapManager {
+    pub fn new(escrow_manager: Arc<EscrowManager>) -> Self {
+        SwapManager {
+            swaps: Arc::new(RwLock::new(HashMap::new())),
+            escrow_manager,
+        }
+    }
+
+    /// Initiator step: generate the preimage/hash, lock the "from" leg in
+    /// an escrow contract, and record the swap as `Created`.
+    pub async fn create_offer(
+        &self,
+        initiator_did: String,
+        counterparty_did: String,
+        from_currency: Currency,
+        from_amount: u64,
+        to_currency: Currency,
+        to_amount: u64,
+    ) -> Result<String> {
+        let preimage: [u8; 32] = rand::random();
+        let hash: [u8; 32] = Sha256::digest(preimage).into();
+
+        let from_escrow_id = self
+            .escrow_manager
+            .create_escrow(initiator_did.clone(), counterparty_did.clone(), Vec::new(), from_amount, None)
+            .await?;
+
+        let now = crate::core::data_structures::get_current_timestamp();
+        let swap_id = uuid::Uuid::new_v4().to_string();
+        let swap = Swap {
+            id: swap_id.clone(),
+            initiator_did,
+            counterparty_did,
+            from_currency,
+            from_amount,
+            to_currency,
+            to_amount,
+            hash,
+            // Stays `None` until `redeem` reveals it — otherwise
+            // `complete`'s "has the initiator actually redeemed?" check
+            // would trivially pass from the moment the offer is created.
+            preimage: None,
+            from_escrow_id,
+            to_escrow_id: None,
+            timeout_initiator: now + INITIATOR_TIMEOUT_SECS,
+            timeout_counterparty: None,
+            state: SwapState::Created,
+            created_at: now,
+        };
+
+        self.swaps.write().await.insert(swap_id.clone(), swap);
+        info!("Created atomic swap offer: {}", swap_id);
+        Ok(swap_id)
+    }
+
+    /// Counterparty step: having seen `hash`, lock the "to" leg under a
+    /// timeout strictly shorter than the initiator's, so the initiator
+    /// can't redeem it and then stall past the first leg's own timeout.
+    pub async fn accept_offer(&self, swap_id: &str, counterparty_did: &str) -> Result<()> {
+        let mut swaps = self.swaps.write().await;
+        let swap = swaps
+            .get_mut(swap_id)
+            .ok_or_else(|| anyhow!("Swap not found: {}", swap_id))?;
+
+        if swap.state != SwapState::Created {
+            return Err(anyhow!("swap {} is not awaiting acceptance", swap_id));
+        }
+        if swap.counterparty_did != counterparty_did {
+            return Err(anyhow!("{} is not the counterparty for swap {}", counterparty_did, swap_id));
+        }
+
+        let now = crate::core::data_structures::get_current_timestamp();
+        let timeout_counterparty = swap.timeout_initiator.saturating_sub(TIMEOUT_MARGIN_SECS);
+        if now >= timeout_counterparty {
+            return Err(anyhow!("too late to accept swap {}: the mirror leg's timeout would already have passed", swap_id));
+        }
+
+        let to_escrow_id = self
+            .escrow_manager
+            .create_escrow(counterparty_did.to_string(), swap.initiator_did.clone(), Vec::new(), swap.to_amount, None)
+            .await?;
+
+        swap.to_escrow_id = Some(to_escrow_id);
+        swap.timeout_counterparty = Some(timeout_counterparty);
+        swap.state = SwapState::Locked;
+        info!("Swap {} locked by counterparty", swap_id);
+        Ok(())
+    }
+
+    /// Initiator step: reveal `preimage` to claim the counterparty's leg.
+    /// This is what makes `preimage` public for the counterparty to read
+    /// back off-chain and use to claim the first leg in turn.
+    pub async fn redeem(&self, swap_id: &str, preimage: &[u8]) -> Result<()> {
+        let to_escrow_id = {
+            let mut swaps = self.swaps.write().await;
+            let swap = swaps
+                .get_mut(swap_id)
+                .ok_or_else(|| anyhow!("Swap not found: {}", swap_id))?;
+
+            if swap.state != SwapState::Locked {
+                return Err(anyhow!("swap {} has no locked mirror leg to redeem", swap_id));
+            }
+            let computed: [u8; 32] = Sha256::digest(preimage).into();
+            if computed != swap.hash {
+                return Err(anyhow!("preimage does not match swap {}'s hash", swap_id));
+            }
+            let to_escrow_id = swap
+                .to_escrow_id
+                .clone()
+                .ok_or_else(|| anyhow!("swap {} has no mirror leg", swap_id))?;
+            swap.preimage = Some(hex::encode(preimage));
+            swap.state = SwapState::Redeemed;
+            to_escrow_id
+        };
+
+        self.escrow_manager
+            .update_state(&to_escrow_id, crate::core::data_structures::EscrowState::Completed)
+            .await?;
+        info!("Swap {} redeemed by initiator", swap_id);
+        Ok(())
+    }
+
+    /// Counterparty step: once the initiator's claim has revealed `s`,
+    /// use it to redeem the "from" leg in turn, completing the swap.
+    pub async fn complete(&self, swap_id: &str) -> Result<()> {
+        let from_escrow_id = {
+            let swaps = self.swaps.read().await;
+            let swap = swaps
+                .get(swap_id)
+                .ok_or_else(|| anyhow!("Swap not found: {}", swap_id))?;
+            if swap.preimage.is_none() {
+                return Err(anyhow!("swap {} has no revealed preimage yet", swap_id));
+            }
+            swap.from_escrow_id.clone()
+        };
+
+        self.escrow_manager
+            .update_state(&from_escrow_id, crate::core::data_structures::EscrowState::Completed)
+            .await?;
+        info!("Swap {} completed by counterparty", swap_id);
+        Ok(())
+    }
+
+    /// Refunds whichever legs have passed their timeout without being
+    /// redeemed: the mirror leg refunds to the counterparty after
+    /// `timeout_counterparty`, and the first leg refunds to the initiator
+    /// after `timeout_initiator` if the counterparty never locked a
+    /// mirror contract at all. Returns the swap ids that were refunded.
+    pub async fn check_timeouts(&self) -> Result<Vec<String>> {
+        let now = crate::core::data_structures::get_current_timestamp();
+        let mut refunded = Vec::new();
+
+        let expired: Vec<(String, String, bool)> = {
+            let swaps = self.swaps.read().await;
+            swaps
+                .values()
+                .filter_map(|swap| match swap.state {
+                    SwapState::Locked if swap.timeout_counterparty.map_or(false, |t| now >= t) => {
+                        swap.to_escrow_id.clone().map(|id| (swap.id.clone(), id, false))
+                    }
+                    SwapState::Created if now >= swap.timeout_initiator => {
+                        Some((swap.id.clone(), swap.from_escrow_id.clone(), true))
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+
+        for (swap_id, escrow_id, refund_initiator_leg) in expired {
+            self.escrow_manager
+                .update_state(&escrow_id, crate::core::data_structures::EscrowState::Refunded)
+                .await?;
+            let mut swaps = self.swaps.write().await;
+            if let Some(swap) = swaps.get_mut(&swap_id) {
+                swap.state = SwapState::Refunded;
+            }
+            info!(
+                "Swap {} auto-refunded its {} leg after timeout",
+                swap_id,
+                if refund_initiator_leg { "initiator" } else { "counterparty" }
+            );
+            refunded.push(swap_id);
+        }
+
+        Ok(refunded)
+    }
+
+    pub async fn get_swap(&self, swap_id: &str) -> Option<Swap> {
+        self.swaps.read().await.get(swap_id).cloned()
+    }
+
+    /// Like `get_swap`, but strips the preimage — what status-reporting
+    /// callers (the API/RPC routes) should use instead of `get_swap`.
+    pub async fn get_swap_status(&self, swap_id: &str) -> Option<SwapStatus> {
+        self.swaps.read().await.get(swap_id).map(SwapStatus::from)
+    }
+}
@@ -5,6 +5,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info};
 
+#[derive(Clone)]
 pub struct TaskEngine {
     pub pending_tasks: Arc<RwLock<HashMap<TaskId, Task>>>,
     pub completed_tasks: Arc<RwLock<HashMap<TaskId, TaskResult>>>,
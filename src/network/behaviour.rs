@@ -0,0 +1,24 @@
+//! The libp2p `NetworkBehaviour` DuxNet nodes run: gossipsub for the four
+//! pub/sub topics plus a Kademlia DHT for service announcements.
+
+use libp2p::kad::store::MemoryStore;
+use libp2p::kad::Behaviour as Kademlia;
+use libp2p::swarm::NetworkBehaviour;
+use libp2p::{gossipsub, identify, PeerId};
+
+#[derive(NetworkBehaviour)]
+pub struct DuxNetBehaviour {
+    pub gossipsub: gossipsub::Behaviour,
+    pub kademlia: Kademlia<MemoryStore>,
+    pub identify: identify::Behaviour,
+}
+
+impl DuxNetBehaviour {
+    pub fn new(local_peer_id: PeerId, gossipsub: gossipsub::Behaviour, identify_config: identify::Config) -> Self {
+        DuxNetBehaviour {
+            gossipsub,
+            kademlia: Kademlia::new(local_peer_id, MemoryStore::new(local_peer_id)),
+            identify: identify::Behaviour::new(identify_config),
+        }
+    }
+}
@@ -0,0 +1,340 @@
+//! Real P2P transport: gossipsub for message broadcast, Kademlia for
+//! service discovery. Announcements are DID-signed so a forged or stale
+//! `ServiceAnnouncement` can be rejected on retrieval instead of trusted
+//! blindly, mirroring the close-group/quorum record model used by other
+//! content-addressed P2P networks.
+
+use super::behaviour::{DuxNetBehaviour, DuxNetBehaviourEvent};
+use super::{Network, NetworkStats, TOPIC_ESCROW, TOPIC_REPUTATION, TOPIC_SERVICES, TOPIC_TASKS};
+use crate::core::data_structures::*;
+use crate::core::dht::DHT;
+use crate::core::identity::{verify_with_public_key, DIDManager};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use futures::StreamExt;
+use libp2p::kad::{self, Quorum, Record};
+use libp2p::swarm::SwarmEvent;
+use libp2p::{gossipsub, identify, noise, tcp, yamux, Multiaddr, PeerId, Swarm};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, error, info, warn};
+
+/// What actually gets written to the Kademlia DHT for a service
+/// announcement: just the metadata. `service.signature` (checked against
+/// the provider's *resolved* DID key, not anything record-supplied) is
+/// already everything a reader needs to reject a forged or tampered
+/// listing — see `verify`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SignedServiceRecord {
+    service: ServiceMetadata,
+}
+
+impl SignedServiceRecord {
+    /// Whether `service.signature` actually matches `provider_did`'s
+    /// *resolved* key over the full canonical `service_message` (all 7
+    /// fields) — mirrors `DHT::service_signature_valid`'s check, so a
+    /// record signed with an attacker's own embedded key can't pass.
+    async fn verify(&self, dht: &DHT) -> bool {
+        let Some(provider) = dht.resolve_did(&self.service.provider_did).await else {
+            return false;
+        };
+        let message = crate::core::identity::service_message(&self.service);
+        verify_with_public_key(&provider.public_key, message.as_bytes(), &self.service.signature)
+    }
+}
+
+fn topic(name: &str) -> gossipsub::IdentTopic {
+    gossipsub::IdentTopic::new(name)
+}
+
+/// An in-flight `query_service` lookup: accumulates verified records as
+/// `FoundRecord` events arrive, grouped by the exact `ServiceMetadata` each
+/// one reports, and only resolves once `quorum` of them *agree* on the
+/// same value — a single forged-but-individually-valid record mixed in
+/// with legitimate ones can't out-vote them by merely adding to the total
+/// count.
+struct PendingQuery {
+    quorum: usize,
+    agreeing: HashMap<String, (ServiceMetadata, usize)>,
+    sender: tokio::sync::oneshot::Sender<Option<ServiceMetadata>>,
+}
+
+pub struct Libp2pNetwork {
+    pub local_peer_id: PeerId,
+    swarm: Arc<Mutex<Swarm<DuxNetBehaviour>>>,
+    is_running: Arc<RwLock<bool>>,
+    connected_peers: Arc<RwLock<HashSet<PeerId>>>,
+    provider_did: String,
+    pending_queries: Arc<RwLock<HashMap<kad::QueryId, PendingQuery>>>,
+    /// Resolves a signer's DID to their public key so inbound messages
+    /// (e.g. `NetworkMessage::EscrowSignature`) can be verified against
+    /// the claimed signer rather than trusted blindly.
+    dht: Arc<DHT>,
+}
+
+impl Libp2pNetwork {
+    pub async fn new(port: u16, did_manager: &DIDManager, dht: Arc<DHT>) -> Result<Self> {
+        let mut swarm = libp2p::SwarmBuilder::with_new_identity()
+            .with_tokio()
+            .with_tcp(tcp::Config::default(), noise::Config::new, yamux::Config::default)?
+            .with_behaviour(|key| {
+                let local_peer_id = PeerId::from(key.public());
+
+                let gossipsub_config = gossipsub::ConfigBuilder::default()
+                    .heartbeat_interval(Duration::from_secs(1))
+                    .validation_mode(gossipsub::ValidationMode::Strict)
+                    .build()
+                    .expect("valid gossipsub config");
+                let gossipsub = gossipsub::Behaviour::new(
+                    gossipsub::MessageAuthenticity::Signed(key.clone()),
+                    gossipsub_config,
+                )
+                .expect("valid gossipsub behaviour");
+
+                let identify_config =
+                    identify::Config::new("/duxnet/1.0.0".to_string(), key.public());
+
+                Ok(DuxNetBehaviour::new(local_peer_id, gossipsub, identify_config))
+            })?
+            .build();
+
+        for name in [TOPIC_SERVICES, TOPIC_TASKS, TOPIC_ESCROW, TOPIC_REPUTATION] {
+            swarm.behaviour_mut().gossipsub.subscribe(&topic(name))?;
+        }
+
+        let listen_addr: Multiaddr = format!("/ip4/0.0.0.0/tcp/{}", port).parse()?;
+        swarm.listen_on(listen_addr)?;
+
+        let local_peer_id = *swarm.local_peer_id();
+        info!("Local libp2p peer ID: {}", local_peer_id);
+
+        Ok(Libp2pNetwork {
+            local_peer_id,
+            swarm: Arc::new(Mutex::new(swarm)),
+            is_running: Arc::new(RwLock::new(false)),
+            connected_peers: Arc::new(RwLock::new(HashSet::new())),
+            provider_did: did_manager.did.id.clone(),
+            pending_queries: Arc::new(RwLock::new(HashMap::new())),
+            dht,
+        })
+    }
+
+    pub async fn dial(&self, addr: Multiaddr) -> Result<()> {
+        self.swarm.lock().await.dial(addr)?;
+        Ok(())
+    }
+
+    async fn handle_swarm_event(&self, event: SwarmEvent<DuxNetBehaviourEvent>) {
+        match event {
+            SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+                self.connected_peers.write().await.insert(peer_id);
+                debug!("Connected to peer: {}", peer_id);
+            }
+            SwarmEvent::ConnectionClosed { peer_id, .. } => {
+                self.connected_peers.write().await.remove(&peer_id);
+                debug!("Disconnected from peer: {}", peer_id);
+            }
+            SwarmEvent::Behaviour(DuxNetBehaviourEvent::Gossipsub(gossipsub::Event::Message {
+                message,
+                ..
+            })) => {
+                match serde_json::from_slice::<NetworkMessage>(&message.data) {
+                    Ok(NetworkMessage::EscrowSignature(escrow_id, signer_did, state, signature)) => {
+                        self.verify_inbound_escrow_signature(&escrow_id, &signer_did, &state, &signature).await;
+                    }
+                    Ok(NetworkMessage::EcashLockedProofs(escrow_id, proofs)) => {
+                        info!("Received {} locked Cashu proof(s) for escrow {}", proofs.len(), escrow_id);
+                    }
+                    Ok(NetworkMessage::EcashUnlockWitness(escrow_id, witness)) => {
+                        info!("Received Cashu unlock witness ({} bytes) for escrow {}", witness.len(), escrow_id);
+                    }
+                    Ok(msg) => debug!("Received gossipsub message: {:?}", msg),
+                    Err(e) => warn!("Failed to decode gossipsub message: {}", e),
+                }
+            }
+            SwarmEvent::Behaviour(DuxNetBehaviourEvent::Kademlia(kad::Event::OutboundQueryProgressed {
+                id,
+                result: kad::QueryResult::GetRecord(Ok(kad::GetRecordOk::FoundRecord(peer_record))),
+                ..
+            })) => {
+                if let Ok(record) = serde_json::from_slice::<SignedServiceRecord>(&peer_record.record.value) {
+                    if record.verify(&self.dht).await {
+                        let mut pending = self.pending_queries.write().await;
+                        if let Some(query) = pending.get_mut(&id) {
+                            // Fingerprint by content so differing records
+                            // (e.g. one forged-but-verifiable listing
+                            // among legitimate ones) accumulate into
+                            // separate buckets instead of one shared count.
+                            let fingerprint = serde_json::to_string(&record.service).unwrap_or_default();
+                            let entry = query
+                                .agreeing
+                                .entry(fingerprint)
+                                .or_insert_with(|| (record.service.clone(), 0));
+                            entry.1 += 1;
+                            if entry.1 >= query.quorum {
+                                let service = entry.0.clone();
+                                if let Some(query) = pending.remove(&id) {
+                                    let _ = query.sender.send(Some(service));
+                                }
+                            }
+                        }
+                    } else {
+                        warn!("Rejected forged/stale service record during DHT lookup");
+                    }
+                }
+            }
+            SwarmEvent::Behaviour(DuxNetBehaviourEvent::Kademlia(kad::Event::OutboundQueryProgressed {
+                id,
+                result: kad::QueryResult::GetRecord(Ok(kad::GetRecordOk::FinishedWithNoAdditionalRecord { .. })),
+                ..
+            })) => {
+                // The query converged across the closest group without
+                // `quorum` agreeing on any single value — nothing here is
+                // trustworthy enough to hand back, so resolve empty rather
+                // than hang until `query_service`'s timeout.
+                if let Some(query) = self.pending_queries.write().await.remove(&id) {
+                    let _ = query.sender.send(None);
+                }
+            }
+            SwarmEvent::Behaviour(DuxNetBehaviourEvent::Kademlia(kad::Event::OutboundQueryProgressed {
+                id,
+                result: kad::QueryResult::GetRecord(Err(_)),
+                ..
+            })) => {
+                if let Some(query) = self.pending_queries.write().await.remove(&id) {
+                    let _ = query.sender.send(None);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Resolves `signer_did`'s key via the DHT and checks `signature` over
+    /// `escrow_id:state` against it, logging (but not otherwise acting on)
+    /// the result — a peer just relays contract approvals, it doesn't yet
+    /// hold its own view of escrow state to act on them.
+    async fn verify_inbound_escrow_signature(&self, escrow_id: &str, signer_did: &str, state: &EscrowState, signature: &[u8]) {
+        let Some(signer) = self.dht.resolve_did(signer_did).await else {
+            warn!("Rejected escrow signature for {}: could not resolve signer DID {}", escrow_id, signer_did);
+            return;
+        };
+        let message = format!("{}:{}", escrow_id, serde_json::to_string(state).unwrap());
+        if verify_with_public_key(&signer.public_key, message.as_bytes(), signature) {
+            debug!("Verified escrow signature from {} for {}", signer_did, escrow_id);
+        } else {
+            warn!("Rejected escrow signature from {} for {}: signature does not match resolved key", signer_did, escrow_id);
+        }
+    }
+}
+
+#[async_trait]
+impl Network for Libp2pNetwork {
+    async fn start(&self) -> Result<()> {
+        info!("Starting libp2p network...");
+        *self.is_running.write().await = true;
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<()> {
+        info!("Stopping libp2p network...");
+        *self.is_running.write().await = false;
+        Ok(())
+    }
+
+    async fn process_events(&self) -> Result<()> {
+        if !*self.is_running.read().await {
+            return Ok(());
+        }
+
+        let event = {
+            let mut swarm = self.swarm.lock().await;
+            match tokio::time::timeout(Duration::from_millis(50), swarm.select_next_some()).await {
+                Ok(event) => event,
+                Err(_) => return Ok(()),
+            }
+        };
+
+        self.handle_swarm_event(event).await;
+        Ok(())
+    }
+
+    async fn publish_message(&self, topic_name: &str, message: &NetworkMessage) -> Result<()> {
+        let data = serde_json::to_vec(message)?;
+        let mut swarm = self.swarm.lock().await;
+        match swarm.behaviour_mut().gossipsub.publish(topic(topic_name), data) {
+            Ok(_) => {
+                debug!("Published message to topic: {}", topic_name);
+                Ok(())
+            }
+            // No peers subscribed yet is not a hard failure for a node
+            // that just booted.
+            Err(gossipsub::PublishError::InsufficientPeers) => Ok(()),
+            Err(e) => Err(anyhow!("gossipsub publish failed: {}", e)),
+        }
+    }
+
+    async fn connected_peers(&self) -> Vec<String> {
+        self.connected_peers
+            .read()
+            .await
+            .iter()
+            .map(|p| p.to_string())
+            .collect()
+    }
+
+    async fn get_stats(&self) -> NetworkStats {
+        NetworkStats {
+            local_peer_id: self.local_peer_id.to_string(),
+            connected_peers: self.connected_peers.read().await.len(),
+            subscribed_topics: 4,
+        }
+    }
+
+    async fn announce_service(&self, service: &ServiceMetadata) -> Result<()> {
+        let signed = SignedServiceRecord { service: service.clone() };
+
+        let key = kad::RecordKey::new(&format!("service:{}", service.id.0));
+        let record = Record {
+            key,
+            value: serde_json::to_vec(&signed)?,
+            publisher: None,
+            expires: None,
+        };
+
+        let mut swarm = self.swarm.lock().await;
+        swarm
+            .behaviour_mut()
+            .kademlia
+            .put_record(record, Quorum::One)
+            .map_err(|e| anyhow!("failed to start put_record query: {:?}", e))?;
+        Ok(())
+    }
+
+    async fn query_service(&self, service_id: &str, quorum: usize) -> Result<Option<ServiceMetadata>> {
+        let key = kad::RecordKey::new(&format!("service:{}", service_id));
+        let quorum = quorum.max(1);
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let query_id = {
+            let mut swarm = self.swarm.lock().await;
+            swarm.behaviour_mut().kademlia.get_record(key)
+        };
+        self.pending_queries
+            .write()
+            .await
+            .insert(query_id, PendingQuery { quorum, agreeing: HashMap::new(), sender: tx });
+
+        match tokio::time::timeout(Duration::from_secs(5), rx).await {
+            // The handler already enforces that `service` only arrives
+            // here once `quorum` records agreed on it.
+            Ok(Ok(service)) => Ok(service),
+            _ => {
+                self.pending_queries.write().await.remove(&query_id);
+                Ok(None)
+            }
+        }
+    }
+}
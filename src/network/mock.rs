@@ -0,0 +1,134 @@
+//! In-memory test double for [`Network`]. Used by unit tests and by any
+//! binary that wants to run a node without touching a real socket.
+
+use super::{Network, NetworkStats, TOPIC_ESCROW, TOPIC_REPUTATION, TOPIC_SERVICES, TOPIC_TASKS};
+use crate::core::data_structures::*;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+
+pub struct MockNetwork {
+    pub local_peer_id: String,
+    pub topics: Arc<RwLock<HashMap<String, String>>>,
+    pub is_running: Arc<RwLock<bool>>,
+    pub connected_peers: Arc<RwLock<Vec<String>>>,
+    pub published: Arc<RwLock<Vec<(String, NetworkMessage)>>>,
+}
+
+impl MockNetwork {
+    pub async fn new(port: u16) -> Result<Self> {
+        let local_peer_id = format!("peer_{}", port);
+
+        info!("Local peer ID: {:?}", local_peer_id);
+
+        let topics = Arc::new(RwLock::new(HashMap::new()));
+        {
+            let mut topics_guard = topics.write().await;
+            topics_guard.insert(TOPIC_SERVICES.to_string(), TOPIC_SERVICES.to_string());
+            topics_guard.insert(TOPIC_TASKS.to_string(), TOPIC_TASKS.to_string());
+            topics_guard.insert(TOPIC_ESCROW.to_string(), TOPIC_ESCROW.to_string());
+            topics_guard.insert(TOPIC_REPUTATION.to_string(), TOPIC_REPUTATION.to_string());
+        }
+
+        Ok(MockNetwork {
+            local_peer_id,
+            topics,
+            is_running: Arc::new(RwLock::new(false)),
+            connected_peers: Arc::new(RwLock::new(Vec::new())),
+            published: Arc::new(RwLock::new(Vec::new())),
+        })
+    }
+
+    /// Test helper: inspect everything that was published so far.
+    pub async fn published_messages(&self) -> Vec<(String, NetworkMessage)> {
+        self.published.read().await.clone()
+    }
+
+    /// Test helper: simulate a peer connecting.
+    pub async fn connect_peer(&self, peer_id: String) {
+        self.connected_peers.write().await.push(peer_id);
+    }
+}
+
+#[async_trait]
+impl Network for MockNetwork {
+    async fn start(&self) -> Result<()> {
+        info!("Starting mock P2P network...");
+        *self.is_running.write().await = true;
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<()> {
+        info!("Stopping mock P2P network...");
+        *self.is_running.write().await = false;
+        Ok(())
+    }
+
+    async fn process_events(&self) -> Result<()> {
+        if !*self.is_running.read().await {
+            return Ok(());
+        }
+        debug!("Processing mock network events...");
+        Ok(())
+    }
+
+    async fn publish_message(&self, topic_name: &str, message: &NetworkMessage) -> Result<()> {
+        let topics = self.topics.read().await;
+        if topics.contains_key(topic_name) {
+            self.published
+                .write()
+                .await
+                .push((topic_name.to_string(), message.clone()));
+            debug!("Published message to topic: {}", topic_name);
+        }
+        Ok(())
+    }
+
+    async fn connected_peers(&self) -> Vec<String> {
+        self.connected_peers.read().await.clone()
+    }
+
+    async fn get_stats(&self) -> NetworkStats {
+        let peers = self.connected_peers().await;
+        let topics = self.topics.read().await;
+
+        NetworkStats {
+            local_peer_id: self.local_peer_id.clone(),
+            connected_peers: peers.len(),
+            subscribed_topics: topics.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn publish_records_message_on_known_topic() {
+        let network = MockNetwork::new(9000).await.unwrap();
+        network.start().await.unwrap();
+
+        network
+            .publish_message(TOPIC_SERVICES, &NetworkMessage::Ping)
+            .await
+            .unwrap();
+
+        let published = network.published_messages().await;
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].0, TOPIC_SERVICES);
+    }
+
+    #[tokio::test]
+    async fn publish_ignores_unknown_topic() {
+        let network = MockNetwork::new(9001).await.unwrap();
+        network
+            .publish_message("not-a-real-topic", &NetworkMessage::Ping)
+            .await
+            .unwrap();
+        assert!(network.published_messages().await.is_empty());
+    }
+}
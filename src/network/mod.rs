@@ -1,104 +1,142 @@
-use crate::core::data_structures::*;
+pub mod behaviour;
+pub mod libp2p_network;
+pub mod mock;
+pub mod nostr;
+
+pub use libp2p_network::Libp2pNetwork;
+pub use mock::MockNetwork;
+pub use nostr::NostrNetwork;
+
+use crate::core::data_structures::NetworkMessage;
 use anyhow::Result;
-use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{debug, info};
+use async_trait::async_trait;
+
+/// Topics every node subscribes to. Kept centralized so the mock and the
+/// real libp2p backend agree on naming.
+pub const TOPIC_SERVICES: &str = "services";
+pub const TOPIC_TASKS: &str = "tasks";
+pub const TOPIC_ESCROW: &str = "escrow";
+pub const TOPIC_REPUTATION: &str = "reputation";
 
-pub struct P2PNetwork {
+/// Abstraction over the P2P transport so the rest of the node doesn't care
+/// whether messages travel over libp2p, Nostr (`nostr::NostrNetwork`), or
+/// an in-memory mock.
+#[async_trait]
+pub trait Network: Send + Sync {
+    async fn start(&self) -> Result<()>;
+    async fn stop(&self) -> Result<()>;
+    async fn process_events(&self) -> Result<()>;
+    async fn publish_message(&self, topic_name: &str, message: &NetworkMessage) -> Result<()>;
+    async fn connected_peers(&self) -> Vec<String>;
+    async fn get_stats(&self) -> NetworkStats;
+
+    /// Write a signed `ServiceAnnouncement` record into the DHT, keyed by
+    /// `ServiceId`. Default transports that have no DHT can simply ignore it.
+    async fn announce_service(&self, _service: &crate::core::data_structures::ServiceMetadata) -> Result<()> {
+        Ok(())
+    }
+
+    /// Query the DHT for providers of a service, requiring `quorum` of the
+    /// closest-group responses to agree before returning a result.
+    async fn query_service(&self, _service_id: &str, _quorum: usize) -> Result<Option<crate::core::data_structures::ServiceMetadata>> {
+        Ok(None)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NetworkStats {
     pub local_peer_id: String,
-    pub topics: Arc<RwLock<HashMap<String, String>>>,
-    pub is_running: Arc<RwLock<bool>>,
-    pub connected_peers: Arc<RwLock<Vec<String>>>,
+    pub connected_peers: usize,
+    pub subscribed_topics: usize,
 }
 
-impl P2PNetwork {
-    pub async fn new(port: u16) -> Result<Self> {
-        let local_peer_id = format!("peer_{}", port);
-        
-        info!("Local peer ID: {:?}", local_peer_id);
-        
-        let topics = Arc::new(RwLock::new(HashMap::new()));
-        {
-            let mut topics_guard = topics.write().await;
-            topics_guard.insert("services".to_string(), "services".to_string());
-            topics_guard.insert("tasks".to_string(), "tasks".to_string());
-            topics_guard.insert("escrow".to_string(), "escrow".to_string());
-            topics_guard.insert("reputation".to_string(), "reputation".to_string());
-        }
-        
-        let is_running = Arc::new(RwLock::new(false));
-        let connected_peers = Arc::new(RwLock::new(Vec::new()));
-        
-        Ok(P2PNetwork {
-            local_peer_id,
-            topics,
-            is_running,
-            connected_peers,
-        })
+/// Backwards-compatible alias: existing call sites referred to `P2PNetwork`
+/// directly. New code should depend on the `Network` trait instead.
+pub type P2PNetwork = Libp2pNetwork;
+
+/// Fans a single logical `Network` out over several transports at once,
+/// e.g. libp2p for low-latency direct links plus Nostr as a NAT/offline
+/// fallback rendezvous. Stats and peers are aggregated across all of them.
+pub struct CompositeNetwork {
+    transports: Vec<std::sync::Arc<dyn Network>>,
+}
+
+impl CompositeNetwork {
+    pub fn new(transports: Vec<std::sync::Arc<dyn Network>>) -> Self {
+        CompositeNetwork { transports }
     }
-    
-    pub async fn start(&self) -> Result<()> {
-        info!("Starting P2P network...");
-        {
-            let mut running = self.is_running.write().await;
-            *running = true;
+}
+
+#[async_trait]
+impl Network for CompositeNetwork {
+    async fn start(&self) -> Result<()> {
+        for t in &self.transports {
+            t.start().await?;
         }
         Ok(())
     }
-    
-    pub async fn stop(&self) -> Result<()> {
-        info!("Stopping P2P network...");
-        {
-            let mut running = self.is_running.write().await;
-            *running = false;
+
+    async fn stop(&self) -> Result<()> {
+        for t in &self.transports {
+            t.stop().await?;
         }
         Ok(())
     }
-    
-    pub async fn process_events(&self) -> Result<()> {
-        // Check if we should stop
-        {
-            let running = self.is_running.read().await;
-            if !*running {
-                return Ok(());
-            }
+
+    async fn process_events(&self) -> Result<()> {
+        for t in &self.transports {
+            t.process_events().await?;
         }
-        
-        // Mock event processing
-        debug!("Processing network events...");
-        
         Ok(())
     }
-    
-    pub async fn publish_message(&self, topic_name: &str, message: &NetworkMessage) -> Result<()> {
-        let topics = self.topics.read().await;
-        if topics.contains_key(topic_name) {
-            debug!("Published message to topic: {}", topic_name);
+
+    async fn publish_message(&self, topic_name: &str, message: &NetworkMessage) -> Result<()> {
+        for t in &self.transports {
+            t.publish_message(topic_name, message).await?;
         }
         Ok(())
     }
-    
-    pub async fn get_peers(&self) -> Vec<String> {
-        let peers = self.connected_peers.read().await;
-        peers.clone()
+
+    async fn connected_peers(&self) -> Vec<String> {
+        let mut peers = Vec::new();
+        for t in &self.transports {
+            peers.extend(t.connected_peers().await);
+        }
+        peers
     }
-    
-    pub async fn get_stats(&self) -> NetworkStats {
-        let peers = self.get_peers().await;
-        let topics = self.topics.read().await;
-        
+
+    async fn get_stats(&self) -> NetworkStats {
+        let mut connected_peers = 0;
+        let mut subscribed_topics = 0;
+        for t in &self.transports {
+            let stats = t.get_stats().await;
+            connected_peers += stats.connected_peers;
+            subscribed_topics = subscribed_topics.max(stats.subscribed_topics);
+        }
         NetworkStats {
-            local_peer_id: self.local_peer_id.clone(),
-            connected_peers: peers.len(),
-            subscribed_topics: topics.len(),
+            local_peer_id: self
+                .transports
+                .first()
+                .map(|_| "composite".to_string())
+                .unwrap_or_default(),
+            connected_peers,
+            subscribed_topics,
         }
     }
-}
 
-#[derive(Debug, Clone)]
-pub struct NetworkStats {
-    pub local_peer_id: String,
-    pub connected_peers: usize,
-    pub subscribed_topics: usize,
-} 
\ No newline at end of file
+    async fn announce_service(&self, service: &crate::core::data_structures::ServiceMetadata) -> Result<()> {
+        for t in &self.transports {
+            t.announce_service(service).await?;
+        }
+        Ok(())
+    }
+
+    async fn query_service(&self, service_id: &str, quorum: usize) -> Result<Option<crate::core::data_structures::ServiceMetadata>> {
+        for t in &self.transports {
+            if let Some(service) = t.query_service(service_id, quorum).await? {
+                return Ok(Some(service));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,207 @@
+//! Nostr relay transport: an alternative coordination layer to libp2p that
+//! survives NAT/offline peers by rendezvousing through public relays
+//! instead of a direct connection. `NetworkMessage`s travel as signed
+//! Nostr events, keyed to the sender's DID so a buyer can find a seller's
+//! endpoints by querying relays for their DID pubkey.
+//!
+//! The relay-level event is signed by an ephemeral Nostr keypair (so it
+//! passes relay validation), but the payload itself carries a DuxNet DID
+//! signature produced by [`DIDManager::sign_message`] — that's the
+//! signature DuxNet peers actually check, so a relay or a MITM can't forge
+//! a message without the sender's DID key regardless of which Nostr
+//! keypair published it.
+
+use super::{Network, NetworkStats, TOPIC_ESCROW, TOPIC_TASKS};
+use crate::core::data_structures::NetworkMessage;
+use crate::core::identity::DIDManager;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use nostr_sdk::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// Replaceable event kind DuxNet uses for coordination messages outside
+/// the task and escrow topics (e.g. `ServiceAnnouncement`); relays that
+/// implement NIP-01 replaceable events keep only the latest one per
+/// (pubkey, kind, d-tag). Task and escrow messages, `EscrowSignature`
+/// included, go out as ephemeral kind 20078 events instead (see
+/// `publish_message`).
+const DUXNET_EVENT_KIND: Kind = Kind::Custom(30078);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SignedEnvelope {
+    sender_did: String,
+    sender_public_key: Vec<u8>,
+    signature: Vec<u8>,
+    topic: String,
+    message: NetworkMessage,
+}
+
+pub struct NostrNetwork {
+    client: Client,
+    did_manager_public_key: Vec<u8>,
+    did: String,
+    secret_key_bytes: Vec<u8>,
+    is_running: Arc<RwLock<bool>>,
+    seen_authors: Arc<RwLock<HashSet<PublicKey>>>,
+}
+
+impl NostrNetwork {
+    pub async fn new(relays: Vec<String>, did_manager: &DIDManager) -> Result<Self> {
+        // A fresh Nostr identity per node; it only has to satisfy relay
+        // validation, DuxNet's own trust model lives in `SignedEnvelope`.
+        let keys = Keys::generate();
+        let client = Client::new(&keys);
+        for relay in &relays {
+            client.add_relay(relay.clone()).await?;
+        }
+        client.connect().await;
+
+        let filter = Filter::new().kind(DUXNET_EVENT_KIND);
+        client.subscribe(vec![filter], None).await;
+
+        info!("Nostr transport connected to {} relay(s)", relays.len());
+
+        Ok(NostrNetwork {
+            client,
+            did_manager_public_key: did_manager.did.public_key.clone(),
+            did: did_manager.did.id.clone(),
+            secret_key_bytes: did_manager.secret_key.clone(),
+            is_running: Arc::new(RwLock::new(false)),
+            seen_authors: Arc::new(RwLock::new(HashSet::new())),
+        })
+    }
+
+    fn sign(&self, message: &NetworkMessage) -> Result<Vec<u8>> {
+        use ed25519_dalek::{Signer, SigningKey};
+        let key_bytes: [u8; 32] = self
+            .secret_key_bytes
+            .clone()
+            .try_into()
+            .map_err(|_| anyhow!("invalid DID secret key length"))?;
+        let signing_key = SigningKey::from_bytes(&key_bytes);
+        let payload = serde_json::to_vec(message)?;
+        Ok(signing_key.sign(&payload).to_bytes().to_vec())
+    }
+
+    fn verify_envelope(envelope: &SignedEnvelope) -> bool {
+        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+        let Ok(key_bytes): Result<[u8; 32], _> = envelope.sender_public_key.clone().try_into() else {
+            return false;
+        };
+        let Ok(verifying_key) = VerifyingKey::from_bytes(&key_bytes) else {
+            return false;
+        };
+        // A DuxNet DID is derived from its public key, so the claimed DID
+        // must be the one `sender_public_key` yields.
+        if envelope.sender_did != format!("did:duxnet:{}", hex::encode(&key_bytes[..16])) {
+            return false;
+        }
+        let Ok(sig_bytes): Result<[u8; 64], _> = envelope.signature.clone().try_into() else {
+            return false;
+        };
+        let signature = Signature::from_bytes(&sig_bytes);
+        let Ok(payload) = serde_json::to_vec(&envelope.message) else {
+            return false;
+        };
+        verifying_key.verify(&payload, &signature).is_ok()
+    }
+
+    /// Query relays for the endpoints a DID has published, by their DuxNet
+    /// DID-derived Nostr filter tag (`#d`).
+    pub async fn find_endpoints_for_did(&self, did: &str) -> Result<Vec<String>> {
+        let filter = Filter::new().kind(DUXNET_EVENT_KIND).identifier(did);
+        let events = self
+            .client
+            .get_events_of(vec![filter], Some(std::time::Duration::from_secs(5)))
+            .await?;
+
+        let mut endpoints = Vec::new();
+        for event in events {
+            if let Ok(envelope) = serde_json::from_str::<SignedEnvelope>(&event.content) {
+                if envelope.sender_did == did && Self::verify_envelope(&envelope) {
+                    if let NetworkMessage::ServiceAnnouncement(service) = envelope.message {
+                        endpoints.push(service.endpoint);
+                    }
+                }
+            }
+        }
+        Ok(endpoints)
+    }
+}
+
+#[async_trait]
+impl Network for NostrNetwork {
+    async fn start(&self) -> Result<()> {
+        *self.is_running.write().await = true;
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<()> {
+        *self.is_running.write().await = false;
+        self.client.disconnect().await?;
+        Ok(())
+    }
+
+    async fn process_events(&self) -> Result<()> {
+        if !*self.is_running.read().await {
+            return Ok(());
+        }
+
+        let mut notifications = self.client.notifications();
+        match tokio::time::timeout(std::time::Duration::from_millis(50), notifications.recv()).await {
+            Ok(Ok(RelayPoolNotification::Event { event, .. })) => {
+                match serde_json::from_str::<SignedEnvelope>(&event.content) {
+                    Ok(envelope) if Self::verify_envelope(&envelope) => {
+                        self.seen_authors.write().await.insert(event.pubkey);
+                        debug!("Received Nostr event from DID {}: {:?}", envelope.sender_did, envelope.message);
+                    }
+                    Ok(_) => warn!("Dropped Nostr event with an invalid DuxNet DID signature"),
+                    Err(e) => warn!("Failed to decode Nostr event content: {}", e),
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn publish_message(&self, topic_name: &str, message: &NetworkMessage) -> Result<()> {
+        let signature = self.sign(message)?;
+        let envelope = SignedEnvelope {
+            sender_did: self.did.clone(),
+            sender_public_key: self.did_manager_public_key.clone(),
+            signature,
+            topic: topic_name.to_string(),
+            message: message.clone(),
+        };
+
+        let is_ephemeral = matches!(topic_name, TOPIC_TASKS | TOPIC_ESCROW);
+        let kind = if is_ephemeral { Kind::Ephemeral(20078) } else { DUXNET_EVENT_KIND };
+
+        let event = EventBuilder::new(kind, serde_json::to_string(&envelope)?, [Tag::Identifier(self.did.clone())])
+            .to_event(self.client.keys())?;
+        self.client.send_event(event).await?;
+        debug!("Published DuxNet message to topic {} over Nostr", topic_name);
+        Ok(())
+    }
+
+    async fn connected_peers(&self) -> Vec<String> {
+        self.seen_authors
+            .read()
+            .await
+            .iter()
+            .map(|pk| pk.to_string())
+            .collect()
+    }
+
+    async fn get_stats(&self) -> NetworkStats {
+        NetworkStats {
+            local_peer_id: self.did.clone(),
+            connected_peers: self.seen_authors.read().await.len(),
+            subscribed_topics: 4,
+        }
+    }
+}
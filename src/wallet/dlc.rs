@@ -0,0 +1,207 @@
+//! Discreet-Log-Contract style escrow: funds release based on an oracle
+//! attesting to a real-world event's outcome, rather than only on m-of-n
+//! signatures like `MultiSigWallet`. Modeled on rust-dlc's
+//! announce-then-attest flow, simplified to this crate's mock ledger:
+//! the oracle's `oracle_pubkey` is committed at announcement time, and
+//! at settlement it signs the winning outcome's label, which `attest`
+//! verifies with the same Ed25519 `Verifier` path `verify_transaction`
+//! already uses.
+
+use crate::wallet::{Currency, Transaction, TransactionStatus, Wallet};
+use anyhow::{anyhow, Result};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DlcState {
+    /// Collateral is locked and the oracle's announcement is committed;
+    /// waiting on attestation or the refund timeout.
+    Announced,
+    Attested,
+    Refunded,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DlcOutcome {
+    pub label: String,
+    pub payout_address: String,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DlcContract {
+    pub id: String,
+    /// Depositors of the locked collateral; any remainder left over
+    /// after a payout refunds evenly across them.
+    pub participants: Vec<String>,
+    #[serde(with = "hex_bytes")]
+    pub oracle_pubkey: [u8; 32],
+    pub event_id: String,
+    pub outcomes: Vec<DlcOutcome>,
+    pub collateral: u64,
+    pub currency: Currency,
+    pub refund_timeout: u64,
+    pub state: DlcState,
+    pub lock_tx_id: Option<String>,
+    pub payout_tx_id: Option<String>,
+    pub created_at: u64,
+}
+
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
+        bytes.try_into().map_err(|_| serde::de::Error::custom("expected 32 bytes"))
+    }
+}
+
+impl DlcContract {
+    /// Announces a new contract and locks `collateral` out of `wallet`.
+    /// Each outcome's payout must fit within the collateral, so a
+    /// winning leg never needs more than was put up.
+    pub fn create_dlc(
+        wallet: &mut Wallet,
+        participants: Vec<String>,
+        oracle_pubkey: [u8; 32],
+        event_id: String,
+        outcomes: Vec<(String, String, u64)>,
+        collateral: u64,
+        currency: Currency,
+        refund_timeout_secs: u64,
+    ) -> Result<Self> {
+        if outcomes.is_empty() {
+            return Err(anyhow!("a DLC needs at least one outcome"));
+        }
+        for (label, _, amount) in &outcomes {
+            if *amount > collateral {
+                return Err(anyhow!("outcome \"{}\" payout ({}) exceeds collateral ({})", label, amount, collateral));
+            }
+        }
+
+        let now = crate::core::data_structures::get_current_timestamp();
+        let id = uuid::Uuid::new_v4().to_string();
+
+        wallet.remove_funds(&currency, collateral)?;
+        let lock_tx = Transaction {
+            id: uuid::Uuid::new_v4().to_string(),
+            from: wallet.did.clone(),
+            to: format!("dlc:{}", id),
+            amount: collateral,
+            currency,
+            timestamp: now,
+            signature: Vec::new(),
+            status: TransactionStatus::Pending,
+            fee: 0,
+            block_height: None,
+            confirmations: 0,
+            memo: Some(format!("DLC {} collateral lock for event \"{}\"", id, event_id)),
+            hashlock: None,
+            timelock: Some(now + refund_timeout_secs),
+            revealed_preimage: None,
+        };
+        let lock_tx_id = lock_tx.id.clone();
+        wallet.transactions.push(lock_tx);
+        wallet.last_activity = now;
+
+        Ok(DlcContract {
+            id,
+            participants,
+            oracle_pubkey,
+            event_id,
+            outcomes: outcomes
+                .into_iter()
+                .map(|(label, payout_address, amount)| DlcOutcome { label, payout_address, amount })
+                .collect(),
+            collateral,
+            currency,
+            refund_timeout: now + refund_timeout_secs,
+            state: DlcState::Announced,
+            lock_tx_id: Some(lock_tx_id),
+            payout_tx_id: None,
+            created_at: now,
+        })
+    }
+
+    /// Settles the contract: verifies `oracle_sig` over `outcome_label`
+    /// against the committed `oracle_pubkey`, pays out the matching
+    /// outcome's leg as a signed `Transaction`, and refunds any
+    /// remaining collateral evenly across `participants`.
+    pub fn attest(&mut self, wallet: &mut Wallet, outcome_label: &str, oracle_sig: &Signature) -> Result<()> {
+        if self.state != DlcState::Announced {
+            return Err(anyhow!("contract is not awaiting attestation"));
+        }
+
+        let oracle_verifying_key = VerifyingKey::from_bytes(&self.oracle_pubkey)
+            .map_err(|e| anyhow!("invalid oracle public key: {}", e))?;
+        oracle_verifying_key
+            .verify(outcome_label.as_bytes(), oracle_sig)
+            .map_err(|_| anyhow!("oracle signature does not match outcome \"{}\"", outcome_label))?;
+
+        let outcome = self
+            .outcomes
+            .iter()
+            .find(|o| o.label == outcome_label)
+            .ok_or_else(|| anyhow!("unknown outcome: {}", outcome_label))?;
+
+        let now = crate::core::data_structures::get_current_timestamp();
+        let mut payout_tx = Transaction {
+            id: uuid::Uuid::new_v4().to_string(),
+            from: format!("dlc:{}", self.id),
+            to: outcome.payout_address.clone(),
+            amount: outcome.amount,
+            currency: self.currency,
+            timestamp: now,
+            signature: Vec::new(),
+            status: TransactionStatus::Confirmed,
+            fee: 0,
+            block_height: None,
+            confirmations: 0,
+            memo: Some(format!("DLC {} payout for outcome \"{}\"", self.id, outcome_label)),
+            hashlock: None,
+            timelock: None,
+            revealed_preimage: None,
+        };
+        wallet.sign_transaction(&mut payout_tx)?;
+        let payout_tx_id = payout_tx.id.clone();
+        wallet.transactions.push(payout_tx);
+
+        let remainder = self.collateral - outcome.amount;
+        if remainder > 0 && !self.participants.is_empty() {
+            let share = remainder / self.participants.len() as u64;
+            if share > 0 {
+                wallet.add_funds(self.currency, share * self.participants.len() as u64);
+            }
+        }
+
+        wallet.last_activity = now;
+        self.payout_tx_id = Some(payout_tx_id);
+        self.state = DlcState::Attested;
+        Ok(())
+    }
+
+    /// Reclaims the full locked collateral once `refund_timeout` has
+    /// passed with no attestation.
+    pub fn refund_after(&mut self, wallet: &mut Wallet) -> Result<()> {
+        if self.state != DlcState::Announced {
+            return Err(anyhow!("contract already settled"));
+        }
+        if crate::core::data_structures::get_current_timestamp() <= self.refund_timeout {
+            return Err(anyhow!("refund timeout has not passed yet"));
+        }
+
+        wallet.add_funds(self.currency, self.collateral);
+        if let Some(lock_tx_id) = &self.lock_tx_id {
+            if let Some(tx) = wallet.transactions.iter_mut().find(|t| &t.id == lock_tx_id) {
+                tx.status = TransactionStatus::Cancelled;
+            }
+        }
+        self.state = DlcState::Refunded;
+        Ok(())
+    }
+}
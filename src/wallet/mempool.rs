@@ -0,0 +1,66 @@
+//! Holds `Pending` transactions awaiting confirmation, so a caller can
+//! no longer just claim a transaction is `Confirmed` and have
+//! `Wallet::process_transaction` believe it. A transaction is admitted
+//! via `submit` only once its signature has already verified (see
+//! `Wallet::receive_funds`), and only becomes eligible to settle once
+//! `tick` has carried it past its currency's configured confirmation
+//! threshold.
+
+use crate::wallet::{Currency, Transaction, TransactionStatus};
+use std::collections::HashMap;
+
+/// Confirmations required before a pending transaction is trusted,
+/// mirroring each chain's real-world reorg risk.
+pub fn confirmation_threshold(currency: Currency) -> u32 {
+    match currency {
+        Currency::BTC => 6,
+        Currency::ETH => 12,
+        Currency::USDC => 12, // ERC-20, inherits Ethereum's finality
+        Currency::LTC => 6,
+        Currency::XMR => 10,
+        Currency::DOGE => 20,
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Mempool {
+    pending: HashMap<String, Transaction>,
+}
+
+impl Mempool {
+    pub fn new() -> Self {
+        Mempool::default()
+    }
+
+    /// Admits an already signature-verified transaction for tracking.
+    pub fn submit(&mut self, mut transaction: Transaction) {
+        transaction.status = TransactionStatus::Pending;
+        self.pending.insert(transaction.id.clone(), transaction);
+    }
+
+    /// Simulates a new block: every pending transaction gains a
+    /// confirmation and is stamped with `block_height`. Returns the ids
+    /// of transactions that just crossed their currency's confirmation
+    /// threshold, ready to be removed via `take`.
+    pub fn tick(&mut self, block_height: u64) -> Vec<String> {
+        let mut ready = Vec::new();
+        for (id, transaction) in self.pending.iter_mut() {
+            transaction.confirmations += 1;
+            transaction.block_height = Some(block_height);
+            if transaction.confirmations >= confirmation_threshold(transaction.currency) {
+                transaction.status = TransactionStatus::Confirmed;
+                ready.push(id.clone());
+            }
+        }
+        ready
+    }
+
+    /// Removes and returns a transaction that `tick` reported as ready.
+    pub fn take(&mut self, id: &str) -> Option<Transaction> {
+        self.pending.remove(id)
+    }
+
+    pub fn pending_transactions(&self) -> Vec<Transaction> {
+        self.pending.values().cloned().collect()
+    }
+}
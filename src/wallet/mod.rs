@@ -1,7 +1,18 @@
+pub mod dlc;
+pub mod mempool;
+pub mod multisig_queue;
+pub mod oracle;
+pub mod payment_request;
+pub mod rate;
+pub mod swap;
+
 use crate::core::data_structures::*;
 use anyhow::Result;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
 use ed25519_dalek::{SigningKey, VerifyingKey, Signature, Signer, Verifier};
 use rand::rngs::OsRng;
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::{debug, info};
@@ -92,6 +103,17 @@ pub struct Wallet {
     pub addresses: HashMap<Currency, String>, // currency -> address
     pub created_at: u64,
     pub last_activity: u64,
+    /// The BIP39 recovery phrase, if this wallet was created via
+    /// [`Wallet::from_mnemonic`] rather than [`Wallet::new`]. All six
+    /// currency addresses and `secret_key` are reproducible from this
+    /// phrase (plus its passphrase) alone.
+    #[serde(default)]
+    pub mnemonic: Option<String>,
+    /// Incoming transactions awaiting confirmation; see
+    /// [`crate::wallet::mempool::Mempool`]. Transient, so it isn't
+    /// carried across serialization.
+    #[serde(skip, default)]
+    pub mempool: mempool::Mempool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -108,6 +130,18 @@ pub struct Transaction {
     pub block_height: Option<u64>,
     pub confirmations: u32,
     pub memo: Option<String>,
+    /// Set on the lock leg of an atomic swap; see [`swap`]. `SHA256` of
+    /// the swap's secret preimage.
+    #[serde(default)]
+    pub hashlock: Option<[u8; 32]>,
+    /// Unix timestamp after which the locked leg can be refunded instead
+    /// of redeemed.
+    #[serde(default)]
+    pub timelock: Option<u64>,
+    /// The preimage revealed on redeem, once known; `None` while only
+    /// `hashlock` is public.
+    #[serde(default)]
+    pub revealed_preimage: Option<[u8; 32]>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -146,11 +180,17 @@ pub struct SendResponse {
     pub fee: u64,
 }
 
+const ENCRYPTED_BACKUP_VERSION: &str = "2.0-encrypted";
+/// Argon2id params for backup encryption: 19 MiB of memory, 2 passes,
+/// 1 lane.
+const BACKUP_KDF_MEMORY_KIB: u32 = 19456;
+const BACKUP_KDF_ITERATIONS: u32 = 2;
+const BACKUP_KDF_PARALLELISM: u32 = 1;
+
 impl Wallet {
     pub fn new(did: String) -> Result<Self> {
         let mut csprng = OsRng;
         let mut secret_bytes = [0u8; 32];
-        use rand::RngCore;
         csprng.fill_bytes(&mut secret_bytes);
         let keypair = SigningKey::from_bytes(&secret_bytes);
         let secret_key_bytes = keypair.to_bytes().to_vec();
@@ -175,9 +215,75 @@ impl Wallet {
             addresses,
             created_at: now,
             last_activity: now,
+            mnemonic: None,
+            mempool: mempool::Mempool::new(),
+        })
+    }
+
+    /// Generates a fresh 12-word BIP39 recovery phrase suitable for
+    /// [`Wallet::from_mnemonic`].
+    pub fn generate_mnemonic() -> Result<String> {
+        let mnemonic = bip39::Mnemonic::generate(12)
+            .map_err(|e| anyhow::anyhow!("failed to generate mnemonic: {}", e))?;
+        Ok(mnemonic.to_string())
+    }
+
+    /// Rebuilds a wallet entirely from `phrase` (and optional `passphrase`),
+    /// following zcash-sync's approach: the phrase derives a 64-byte seed
+    /// via PBKDF2-HMAC-SHA512 (2048 rounds, salt `"mnemonic" + passphrase`),
+    /// and each currency's `SigningKey` is derived from that seed via an
+    /// HKDF path keyed on the currency symbol, so every address - and the
+    /// legacy `secret_key` - is reproducible from the phrase alone.
+    pub fn from_mnemonic(phrase: &str, passphrase: &str, did: String) -> Result<Self> {
+        let mnemonic = bip39::Mnemonic::parse(phrase)
+            .map_err(|e| anyhow::anyhow!("invalid mnemonic: {}", e))?;
+        let seed = mnemonic.to_seed(passphrase);
+
+        // Kept as the wallet's general-purpose signing key for backward
+        // compatibility with `get_keypair`/`sign_transaction`.
+        let keypair = Self::derive_currency_key(&seed, None)?;
+        let secret_key_bytes = keypair.to_bytes().to_vec();
+
+        let mut balances = HashMap::new();
+        let mut addresses = HashMap::new();
+
+        for currency in [Currency::BTC, Currency::ETH, Currency::USDC, Currency::LTC, Currency::XMR, Currency::DOGE] {
+            let currency_keypair = Self::derive_currency_key(&seed, Some(currency))?;
+            balances.insert(currency, currency.initial_balance());
+            addresses.insert(currency, Self::generate_address(&currency, &currency_keypair.verifying_key()));
+        }
+
+        let now = get_current_timestamp();
+
+        Ok(Wallet {
+            did,
+            secret_key: secret_key_bytes,
+            balances,
+            transactions: Vec::new(),
+            preferred_currency: Currency::USDC,
+            addresses,
+            created_at: now,
+            last_activity: now,
+            mnemonic: Some(mnemonic.to_string()),
+            mempool: mempool::Mempool::new(),
         })
     }
 
+    /// Derives a `SigningKey` from a BIP39 seed via HKDF-SHA512, using the
+    /// currency symbol as the BIP32-style derivation path; `None` derives
+    /// the wallet's general-purpose key.
+    fn derive_currency_key(seed: &[u8], currency: Option<Currency>) -> Result<SigningKey> {
+        let hk = hkdf::Hkdf::<sha2::Sha512>::new(None, seed);
+        let info = match currency {
+            Some(currency) => format!("duxnet/{}", currency.symbol()),
+            None => "duxnet/master".to_string(),
+        };
+        let mut okm = [0u8; 32];
+        hk.expand(info.as_bytes(), &mut okm)
+            .map_err(|e| anyhow::anyhow!("failed to derive {} key: {}", info, e))?;
+        Ok(SigningKey::from_bytes(&okm))
+    }
+
     pub fn set_preferred_currency(&mut self, currency: Currency) {
         self.preferred_currency = currency;
         self.last_activity = get_current_timestamp();
@@ -247,16 +353,8 @@ impl Wallet {
         currency.format_amount(amount)
     }
 
-    pub fn get_total_balance_usd(&self) -> f64 {
-        // Simplified USD conversion rates (in real app, these would come from price feeds)
-        let rates = HashMap::from([
-            (Currency::BTC, 45000.0),
-            (Currency::ETH, 3000.0),
-            (Currency::USDC, 1.0),
-            (Currency::LTC, 150.0),
-            (Currency::XMR, 200.0),
-            (Currency::DOGE, 0.08),
-        ]);
+    pub fn get_total_balance_usd(&self, oracle: &dyn crate::wallet::oracle::PriceOracle) -> f64 {
+        let rates = oracle.rates();
 
         let mut total_usd = 0.0;
         for (currency, balance) in &self.balances {
@@ -323,6 +421,9 @@ impl Wallet {
             block_height: None,
             confirmations: 0,
             memo: request.memo.clone(),
+            hashlock: None,
+            timelock: None,
+            revealed_preimage: None,
         };
         
         // Remove funds from wallet
@@ -342,42 +443,58 @@ impl Wallet {
         })
     }
 
-    pub fn receive_funds(&mut self, from_address: String, amount: u64, currency: Currency, 
-                        transaction_id: String, signature: Vec<u8>) -> Result<()> {
+    /// Admits an incoming transaction into the mempool once its signature
+    /// has actually been checked against `sender_public_key` — a caller can
+    /// no longer credit funds just by asserting a transaction happened.
+    /// Funds land in `balances` only once [`Wallet::advance_mempool`] carries
+    /// it past its currency's confirmation threshold.
+    pub fn receive_funds(&mut self, from_address: String, amount: u64, currency: Currency,
+                        transaction_id: String, fee: u64, signature: Vec<u8>, sender_public_key: &[u8]) -> Result<()> {
         let from_address_clone = from_address.clone();
-        // Verify the transaction signature
-                let _message = format!("{}:{}:{}:{}:{}",
-            transaction_id, from_address, self.did, amount, currency.symbol());
-        
-        // In a real implementation, you'd verify the signature here
-        // For now, we'll just accept it
-        
+
         let transaction = Transaction {
             id: transaction_id,
             from: from_address,
             to: self.did.clone(),
             amount,
-            currency: currency.clone(),
+            currency,
             timestamp: get_current_timestamp(),
             signature,
-            status: TransactionStatus::Confirmed,
-            fee: 0,
-            block_height: Some(0), // Mock block height
-            confirmations: 6, // Mock confirmations
+            status: TransactionStatus::Pending,
+            fee,
+            block_height: None,
+            confirmations: 0,
             memo: None,
+            hashlock: None,
+            timelock: None,
+            revealed_preimage: None,
         };
-        
-        // Add funds to wallet
-        self.add_funds(currency, amount);
-        
-        // Add transaction to history
-        self.transactions.push(transaction);
+
+        if !self.verify_transaction(&transaction, sender_public_key) {
+            return Err(anyhow::anyhow!("Invalid transaction signature"));
+        }
+
+        self.mempool.submit(transaction);
         self.last_activity = get_current_timestamp();
-        
-        info!("Received {} from {}", currency.format_amount(amount), from_address_clone);
+
+        info!("Queued {} from {} pending confirmation", currency.format_amount(amount), from_address_clone);
         Ok(())
     }
 
+    /// Advances the mempool by one simulated block, settling any
+    /// transaction that just crossed its currency's confirmation
+    /// threshold via [`Wallet::process_transaction`]. Returns the ids of
+    /// the transactions that were settled.
+    pub fn advance_mempool(&mut self, block_height: u64) -> Result<Vec<String>> {
+        let ready = self.mempool.tick(block_height);
+        for id in &ready {
+            if let Some(transaction) = self.mempool.take(id) {
+                self.process_transaction(&transaction)?;
+            }
+        }
+        Ok(ready)
+    }
+
     pub fn calculate_fee(&self, currency: &Currency) -> u64 {
         // Simplified fee calculation (in real app, this would be dynamic)
         match currency {
@@ -398,13 +515,14 @@ impl Wallet {
         
         let transaction_id = uuid::Uuid::new_v4().to_string();
         let timestamp = get_current_timestamp();
-        
-        let message = format!("{}:{}:{}:{}:{}", 
-            transaction_id, self.did, to, amount, currency.symbol());
-        
+        let fee = 0;
+
+        let message = format!("{}:{}:{}:{}:{}:{}",
+            transaction_id, self.did, to, amount, currency.symbol(), fee);
+
         let keypair = self.get_keypair()?;
         let signature = keypair.sign(message.as_bytes()).to_bytes().to_vec();
-        
+
         let transaction = Transaction {
             id: transaction_id,
             from: self.did.clone(),
@@ -414,20 +532,23 @@ impl Wallet {
             timestamp,
             signature,
             status: TransactionStatus::Pending,
-            fee: 0,
+            fee,
             block_height: None,
             confirmations: 0,
             memo: None,
+            hashlock: None,
+            timelock: None,
+            revealed_preimage: None,
         };
-        
+
         Ok(transaction)
     }
 
     pub fn sign_transaction(&self, transaction: &mut Transaction) -> Result<()> {
-        let message = format!("{}:{}:{}:{}:{}", 
-            transaction.id, transaction.from, transaction.to, 
-            transaction.amount, transaction.currency.symbol());
-        
+        let message = format!("{}:{}:{}:{}:{}:{}",
+            transaction.id, transaction.from, transaction.to,
+            transaction.amount, transaction.currency.symbol(), transaction.fee);
+
         let keypair = self.get_keypair()?;
         let signature = keypair.sign(message.as_bytes()).to_bytes().to_vec();
         transaction.signature = signature;
@@ -437,10 +558,11 @@ impl Wallet {
     }
 
     pub fn verify_transaction(&self, transaction: &Transaction, public_key: &[u8]) -> bool {
-        let message = format!("{}:{}:{}:{}:{}", 
-            transaction.id, transaction.from, transaction.to, 
-            transaction.amount, transaction.currency.symbol());
-        
+        let message = format!("{}:{}:{}:{}:{}:{}",
+            transaction.id, transaction.from, transaction.to,
+            transaction.amount, transaction.currency.symbol(), transaction.fee);
+
+
         if public_key.len() == 32 {
             if let Ok(verifying_key) = VerifyingKey::from_bytes(&public_key.try_into().unwrap()) {
                 if transaction.signature.len() == 64 {
@@ -455,12 +577,25 @@ impl Wallet {
     }
 
     pub fn process_transaction(&mut self, transaction: &Transaction) -> Result<()> {
-        // Verify the transaction signature
-        if !self.verify_transaction(transaction, &self.get_public_key()?) {
+        // Outgoing transactions were signed with our own key, so we can
+        // re-check them here; incoming ones were already verified against
+        // the sender's key when `receive_funds` admitted them to the mempool.
+        if transaction.from == self.did && !self.verify_transaction(transaction, &self.get_public_key()?) {
             return Err(anyhow::anyhow!("Invalid transaction signature"));
         }
-        
-        match transaction.status {
+
+        // Never trust a caller-supplied `Confirmed` status outright: it only
+        // counts once the transaction has cleared its currency's
+        // confirmation threshold (see `mempool::confirmation_threshold`).
+        let effective_status = if transaction.status == TransactionStatus::Confirmed
+            && transaction.confirmations < mempool::confirmation_threshold(transaction.currency)
+        {
+            TransactionStatus::Pending
+        } else {
+            transaction.status
+        };
+
+        match effective_status {
             TransactionStatus::Confirmed => {
                 if transaction.to == self.did {
                     // We're receiving funds
@@ -560,6 +695,8 @@ impl Wallet {
             addresses,
             created_at: now,
             last_activity: now,
+            mnemonic: None,
+            mempool: mempool::Mempool::new(),
         })
     }
 
@@ -567,22 +704,128 @@ impl Wallet {
         let wallet_data = serde_json::json!({
             "did": self.did,
             "secret_key": general_purpose::STANDARD.encode(&self.secret_key),
+            "mnemonic": self.mnemonic,
             "preferred_currency": self.preferred_currency,
             "created_at": self.created_at,
             "backup_version": "1.0"
         });
-        
+
         Ok(wallet_data.to_string())
     }
 
     pub fn restore_wallet(backup_data: &str) -> Result<Self> {
         let wallet_data: serde_json::Value = serde_json::from_str(backup_data)?;
-        
+
         let did = wallet_data["did"].as_str().unwrap().to_string();
         let secret_key_encoded = wallet_data["secret_key"].as_str().unwrap();
         let secret_key_bytes = general_purpose::STANDARD.decode(secret_key_encoded)?;
-        
-        Self::import_private_key(secret_key_bytes, did)
+
+        let mut wallet = Self::import_private_key(secret_key_bytes, did)?;
+        wallet.mnemonic = wallet_data["mnemonic"].as_str().map(|s| s.to_string());
+        Ok(wallet)
+    }
+
+    /// Like `backup_wallet`, but the payload is sealed behind a
+    /// password instead of stored as plain base64 JSON. A 32-byte key is
+    /// derived with Argon2id over a random 16-byte salt, then used to
+    /// encrypt the payload with `ChaCha20Poly1305` under a random
+    /// 12-byte nonce. The envelope carries everything needed to reverse
+    /// this (`kdf_params`, `salt`, `nonce`) except the password itself.
+    pub fn backup_wallet_encrypted(&self, password: &str) -> Result<String> {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+
+        let key_bytes = Self::derive_backup_key(password, &salt)?;
+
+        let mut nonce_bytes = [0u8; 12];
+        OsRng.fill_bytes(&mut nonce_bytes);
+
+        let plaintext = serde_json::json!({
+            "did": self.did,
+            "secret_key": general_purpose::STANDARD.encode(&self.secret_key),
+            "mnemonic": self.mnemonic,
+            "preferred_currency": self.preferred_currency,
+            "created_at": self.created_at,
+        })
+        .to_string();
+
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), plaintext.as_bytes())
+            .map_err(|e| anyhow::anyhow!("failed to encrypt wallet backup: {}", e))?;
+
+        let envelope = serde_json::json!({
+            "backup_version": ENCRYPTED_BACKUP_VERSION,
+            "kdf": "argon2id",
+            "kdf_params": {
+                "memory_kib": BACKUP_KDF_MEMORY_KIB,
+                "iterations": BACKUP_KDF_ITERATIONS,
+                "parallelism": BACKUP_KDF_PARALLELISM,
+            },
+            "salt": general_purpose::STANDARD.encode(salt),
+            "nonce": general_purpose::STANDARD.encode(nonce_bytes),
+            "ciphertext": general_purpose::STANDARD.encode(ciphertext),
+        });
+
+        Ok(envelope.to_string())
+    }
+
+    /// Reverses `backup_wallet_encrypted`. Fails cleanly (rather than
+    /// producing garbage) when `password` is wrong, since an
+    /// authentication-tag mismatch makes `ChaCha20Poly1305::decrypt`
+    /// itself fail.
+    pub fn restore_wallet_encrypted(data: &str, password: &str) -> Result<Self> {
+        let envelope: serde_json::Value = serde_json::from_str(data)?;
+
+        let version = envelope["backup_version"].as_str().unwrap_or("");
+        if version != ENCRYPTED_BACKUP_VERSION {
+            return Err(anyhow::anyhow!("unsupported encrypted backup version: {}", version));
+        }
+
+        let salt = general_purpose::STANDARD.decode(
+            envelope["salt"].as_str().ok_or_else(|| anyhow::anyhow!("backup missing salt"))?,
+        )?;
+        let nonce_bytes = general_purpose::STANDARD.decode(
+            envelope["nonce"].as_str().ok_or_else(|| anyhow::anyhow!("backup missing nonce"))?,
+        )?;
+        let ciphertext = general_purpose::STANDARD.decode(
+            envelope["ciphertext"]
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("backup missing ciphertext"))?,
+        )?;
+
+        let key_bytes = Self::derive_backup_key(password, &salt)?;
+
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&nonce_bytes), ciphertext.as_ref())
+            .map_err(|_| anyhow::anyhow!("failed to decrypt wallet backup: wrong password or corrupted data"))?;
+
+        let wallet_data: serde_json::Value = serde_json::from_slice(&plaintext)?;
+        let did = wallet_data["did"].as_str().unwrap().to_string();
+        let secret_key_bytes =
+            general_purpose::STANDARD.decode(wallet_data["secret_key"].as_str().unwrap())?;
+
+        let mut wallet = Self::import_private_key(secret_key_bytes, did)?;
+        wallet.mnemonic = wallet_data["mnemonic"].as_str().map(|s| s.to_string());
+        Ok(wallet)
+    }
+
+    fn derive_backup_key(password: &str, salt: &[u8]) -> Result<[u8; 32]> {
+        let params = argon2::Params::new(
+            BACKUP_KDF_MEMORY_KIB,
+            BACKUP_KDF_ITERATIONS,
+            BACKUP_KDF_PARALLELISM,
+            Some(32),
+        )
+        .map_err(|e| anyhow::anyhow!("invalid Argon2 params: {}", e))?;
+        let argon2 = argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+
+        let mut key_bytes = [0u8; 32];
+        argon2
+            .hash_password_into(password.as_bytes(), salt, &mut key_bytes)
+            .map_err(|e| anyhow::anyhow!("failed to derive backup key: {}", e))?;
+        Ok(key_bytes)
     }
 }
 
@@ -594,76 +837,245 @@ pub struct MultiSigWallet {
     pub threshold: usize,
     pub balance: u64,
     pub currency: Currency,
-    pub pending_transactions: Vec<MultiSigTransaction>,
+    pub pending_transactions: multisig_queue::MultiSigTransactionQueue,
+    /// Next value handed out as a `MultiSigTransaction::numeric_id`.
+    next_numeric_id: u64,
 }
 
+/// Default caps on `MultiSigWallet::pending_transactions` before the
+/// queue starts evicting lower-priority entries to make room.
+const DEFAULT_QUEUE_MAX_TOTAL: usize = 500;
+const DEFAULT_QUEUE_MAX_PER_SENDER: usize = 50;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MultiSigTransaction {
     pub id: String,
+    /// Participant who proposed this transaction.
+    pub proposer: String,
     pub to: String,
     pub amount: u64,
     pub currency: Currency,
+    /// The action being proposed, e.g. `"transfer"` or
+    /// `"contract_creation"` (the latter implied whenever `to` is empty).
+    pub method: String,
+    /// Sequence number within `proposer`'s own nonce space — used to tell
+    /// an immediately-actionable proposal from one still waiting on an
+    /// earlier sequence to land (see `get_queued_transactions_by_sender`).
+    pub sequence: u64,
+    /// Monotonically increasing id, scoped to the wallet, mirroring how
+    /// established multisig actors track a proposal by a plain integer
+    /// rather than its opaque `id` string.
+    pub numeric_id: u64,
+    /// Addresses that have recorded an approval via `approve_transaction`.
+    /// Tracked separately from `signatures`, since an approval vote
+    /// doesn't require a signature to already be attached.
+    pub approved: Vec<String>,
+    /// Block/epoch height past which this proposal is stale and eligible
+    /// for `MultiSigWallet::prune_expired` to garbage-collect.
+    pub expiry_epoch: Option<u64>,
     pub signatures: HashMap<String, Vec<u8>>,
     pub status: TransactionStatus,
     pub created_at: u64,
 }
 
+/// Restricts [`MultiSigWallet::get_pending_transactions_filtered`] to the
+/// subset of pending proposals a caller actually wants, so a wallet that
+/// has accumulated many unsigned proposals doesn't have to hand all of
+/// them back just to answer a narrow query. Every field is optional and
+/// filters are combined with AND.
+#[derive(Debug, Clone, Default)]
+pub struct PendingFilter {
+    /// Only proposals from this participant.
+    pub proposer: Option<String>,
+    /// Only proposals sending to this address.
+    pub to: Option<String>,
+    pub min_amount: Option<u64>,
+    /// Only proposals with this `method`. Pass `Some("contract_creation")`
+    /// to match proposals whose `to` is empty regardless of their stored
+    /// `method` string.
+    pub method: Option<String>,
+}
+
+impl PendingFilter {
+    fn matches(&self, transaction: &MultiSigTransaction) -> bool {
+        if let Some(proposer) = &self.proposer {
+            if &transaction.proposer != proposer {
+                return false;
+            }
+        }
+        if let Some(to) = &self.to {
+            if &transaction.to != to {
+                return false;
+            }
+        }
+        if let Some(min_amount) = self.min_amount {
+            if transaction.amount < min_amount {
+                return false;
+            }
+        }
+        if let Some(method) = &self.method {
+            let is_contract_creation = transaction.to.is_empty();
+            if method == "contract_creation" {
+                if !is_contract_creation {
+                    return false;
+                }
+            } else if &transaction.method != method {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 impl MultiSigWallet {
     pub fn new(participants: Vec<String>, threshold: usize, currency: Currency) -> Self {
         let address = format!("multisig_{}", uuid::Uuid::new_v4().to_string()[..8].to_string());
-        
+
+        let verifier = multisig_queue::VerifierOptions {
+            valid_signers: participants.clone(),
+            threshold,
+            min_value: 0,
+        };
+        let limits = multisig_queue::QueueLimits {
+            max_total: DEFAULT_QUEUE_MAX_TOTAL,
+            max_per_sender: DEFAULT_QUEUE_MAX_PER_SENDER,
+        };
+
         MultiSigWallet {
             address,
             participants,
             threshold,
             balance: 0,
             currency,
-            pending_transactions: Vec::new(),
+            pending_transactions: multisig_queue::MultiSigTransactionQueue::new(limits, verifier),
+            next_numeric_id: 0,
         }
     }
 
+    /// Updates the queue's verification constraints (current signer set,
+    /// approval threshold, minimum value) without rebuilding it.
+    pub fn set_verifier_options(&mut self, options: multisig_queue::VerifierOptions) {
+        self.pending_transactions.set_verifier_options(options);
+    }
+
     pub fn add_funds(&mut self, amount: u64) {
         self.balance += amount;
         info!("Added {} to multisig wallet", self.currency.format_amount(amount));
     }
 
-    pub fn create_transaction(&mut self, to: String, amount: u64) -> Result<String> {
+    pub fn create_transaction(
+        &mut self,
+        proposer: String,
+        sequence: u64,
+        to: String,
+        amount: u64,
+        method: String,
+        expiry_epoch: Option<u64>,
+    ) -> Result<String> {
         if amount > self.balance {
             return Err(anyhow::anyhow!("Insufficient balance in multisig wallet"));
         }
 
         let transaction_id = uuid::Uuid::new_v4().to_string();
+        let numeric_id = self.next_numeric_id;
+        self.next_numeric_id += 1;
         let to_clone = to.clone();
         let transaction = MultiSigTransaction {
             id: transaction_id.clone(),
+            proposer,
+            sequence,
+            numeric_id,
+            approved: Vec::new(),
+            expiry_epoch,
             to,
             amount,
             currency: self.currency.clone(),
+            method,
             signatures: HashMap::new(),
             status: TransactionStatus::Pending,
             created_at: get_current_timestamp(),
         };
 
-        self.pending_transactions.push(transaction);
-        info!("Created multisig transaction: {} {} to {}", 
+        self.pending_transactions.import(transaction)?;
+        info!("Created multisig transaction: {} {} to {}",
             self.currency.format_amount(amount), self.currency.symbol(), to_clone);
 
         Ok(transaction_id)
     }
 
+    /// Records `approver`'s approval of a pending transaction, rejecting a
+    /// second approval from the same signer. Returns whether the
+    /// transaction now has enough approvals to meet the wallet's threshold.
+    pub fn approve_transaction(&mut self, transaction_id: &str, approver: String) -> Result<bool> {
+        if !self.participants.contains(&approver) {
+            return Err(anyhow::anyhow!("Approver is not a participant"));
+        }
+
+        let transaction = self
+            .pending_transactions
+            .get_mut(transaction_id)
+            .ok_or_else(|| anyhow::anyhow!("Transaction not found"))?;
+
+        if transaction.approved.contains(&approver) {
+            return Err(anyhow::anyhow!("{} has already approved this transaction", approver));
+        }
+
+        transaction.approved.push(approver);
+        Ok(transaction.approved.len() >= self.threshold)
+    }
+
+    /// How many more approvals `transaction_id` needs to meet the
+    /// wallet's threshold, or `0` if it already has enough.
+    pub fn approvals_needed(&self, transaction_id: &str) -> Result<usize> {
+        let transaction = self
+            .pending_transactions
+            .iter()
+            .find(|transaction| transaction.id == transaction_id)
+            .ok_or_else(|| anyhow::anyhow!("Transaction not found"))?;
+        Ok(self.threshold.saturating_sub(transaction.approved.len()))
+    }
+
+    /// Removes pending transactions whose `expiry_epoch` has passed as of
+    /// `current_epoch` — proposals that will never reach quorum in time
+    /// shouldn't linger in the queue forever. Already-confirmed
+    /// transactions are left alone.
+    pub fn prune_expired(&mut self, current_epoch: u64) -> Vec<MultiSigTransaction> {
+        let expired_ids: Vec<String> = self
+            .pending_transactions
+            .iter()
+            .filter(|transaction| {
+                transaction.status == TransactionStatus::Pending
+                    && transaction.expiry_epoch.is_some_and(|expiry| current_epoch > expiry)
+            })
+            .map(|transaction| transaction.id.clone())
+            .collect();
+
+        expired_ids
+            .into_iter()
+            .filter_map(|id| self.pending_transactions.remove(&id))
+            .collect()
+    }
+
+    /// Admits a batch of externally-proposed transactions straight into
+    /// the queue, verifying and bounding each independently; one
+    /// rejection doesn't block the rest of the batch.
+    pub fn import_transactions(&mut self, transactions: Vec<MultiSigTransaction>) -> Vec<Result<()>> {
+        self.pending_transactions.import_batch(transactions)
+    }
+
     pub fn add_signature(&mut self, transaction_id: &str, signer: String, signature: Vec<u8>) -> Result<bool> {
-        if let Some(transaction) = self.pending_transactions.iter_mut().find(|t| t.id == transaction_id) {
-            if !self.participants.contains(&signer) {
-                return Err(anyhow::anyhow!("Signer is not a participant"));
-            }
+        if !self.participants.contains(&signer) {
+            return Err(anyhow::anyhow!("Signer is not a participant"));
+        }
 
+        if let Some(transaction) = self.pending_transactions.get_mut(transaction_id) {
             transaction.signatures.insert(signer, signature);
 
             // Check if we have enough signatures
             if transaction.signatures.len() >= self.threshold {
                 transaction.status = TransactionStatus::Confirmed;
                 self.balance -= transaction.amount;
-                info!("Multisig transaction {} confirmed with {} signatures", 
+                info!("Multisig transaction {} confirmed with {} signatures",
                     transaction_id, transaction.signatures.len());
                 return Ok(true);
             }
@@ -674,6 +1086,101 @@ impl MultiSigWallet {
     }
 
     pub fn get_pending_transactions(&self) -> Vec<MultiSigTransaction> {
-        self.pending_transactions.clone()
+        self.pending_transactions.all_transactions()
+    }
+
+    /// All queued proposals regardless of state, identical to
+    /// `get_pending_transactions` — named to match `ready_transactions`.
+    pub fn all_transactions(&self) -> Vec<MultiSigTransaction> {
+        self.pending_transactions.all_transactions()
+    }
+
+    /// Proposals that have cleared the approval threshold and reached
+    /// the front of their proposer's sequence — genuinely executable now.
+    pub fn ready_transactions(&self) -> Vec<MultiSigTransaction> {
+        self.pending_transactions.ready()
+    }
+
+    /// Like `get_pending_transactions`, but applies `filter` before
+    /// cloning anything, so transactions that don't match are never
+    /// allocated, then truncates to `limit` to keep the response bounded.
+    pub fn get_pending_transactions_filtered(
+        &self,
+        filter: PendingFilter,
+        limit: Option<usize>,
+    ) -> Vec<MultiSigTransaction> {
+        let matches = self
+            .pending_transactions
+            .iter()
+            .filter(|transaction| filter.matches(transaction))
+            .cloned();
+
+        match limit {
+            Some(limit) => matches.take(limit).collect(),
+            None => matches.collect(),
+        }
+    }
+
+    /// Splits `sender`'s proposals, ordered by `sequence`, into the
+    /// contiguous run starting at their lowest sequence (immediately
+    /// actionable) and everything from the first gap onward (still
+    /// waiting on a predecessor).
+    fn partition_by_sender(&self, sender: &str) -> (Vec<MultiSigTransaction>, Vec<MultiSigTransaction>) {
+        let mut ordered: Vec<&MultiSigTransaction> = self
+            .pending_transactions
+            .iter()
+            .filter(|transaction| transaction.proposer == sender)
+            .collect();
+        ordered.sort_by_key(|transaction| transaction.sequence);
+
+        let mut pending = Vec::new();
+        let mut queued = Vec::new();
+        let mut expected_next = None;
+        for transaction in ordered {
+            match expected_next {
+                Some(expected) if transaction.sequence != expected => queued.push(transaction.clone()),
+                _ => {
+                    expected_next = Some(transaction.sequence + 1);
+                    pending.push(transaction.clone());
+                }
+            }
+        }
+        (pending, queued)
+    }
+
+    /// Proposals from `sender` that are immediately actionable: their
+    /// sequence number has no gap before it.
+    pub fn get_pending_transactions_by_sender(&self, sender: &str) -> Vec<MultiSigTransaction> {
+        self.partition_by_sender(sender).0
+    }
+
+    /// Proposals from `sender` still waiting on an earlier sequence
+    /// number to land before a co-signer can act on them.
+    pub fn get_queued_transactions_by_sender(&self, sender: &str) -> Vec<MultiSigTransaction> {
+        self.partition_by_sender(sender).1
+    }
+
+    /// The next sequence number `address` should use for a new proposal.
+    /// With `include_pending`, this also counts proposals still awaiting
+    /// signatures, not just confirmed ones, so a proposal submitted right
+    /// after another that is still pending doesn't reuse its sequence
+    /// number the way it would if only mined transactions counted.
+    /// Proposals removed by `prune_expired` or evicted from a full queue no
+    /// longer count.
+    pub fn get_transaction_count(&self, address: &str, include_pending: bool) -> u64 {
+        let confirmed = self
+            .pending_transactions
+            .iter()
+            .filter(|transaction| transaction.proposer == address && transaction.status == TransactionStatus::Confirmed)
+            .count() as u64;
+        if !include_pending {
+            return confirmed;
+        }
+        let pending = self
+            .pending_transactions
+            .iter()
+            .filter(|transaction| transaction.proposer == address && transaction.status == TransactionStatus::Pending)
+            .count() as u64;
+        confirmed + pending
     }
 } 
\ No newline at end of file
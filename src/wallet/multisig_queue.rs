@@ -0,0 +1,195 @@
+//! A bounded, verified holding area for `MultiSigTransaction` proposals —
+//! the mempool-style admission and ordering layer backing
+//! `MultiSigWallet::pending_transactions`. Unlike a bare `Vec`, an entry is
+//! checked against the current signer set and a minimum value at import
+//! time, the queue can't grow past configured limits, and when it's full
+//! the lowest-priority entry is evicted to make room for a higher-priority
+//! one instead of simply refusing it.
+
+use crate::wallet::MultiSigTransaction;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Constraints that can legitimately change over a multisig wallet's
+/// lifetime — re-keying, a new minimum transfer policy — without
+/// requiring the queue itself to be rebuilt. See `MultiSigTransactionQueue::set_verifier_options`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifierOptions {
+    /// Addresses currently allowed to propose or sign a transaction.
+    pub valid_signers: Vec<String>,
+    /// Signatures required before a transaction is considered settled.
+    pub threshold: usize,
+    /// Proposals below this value are rejected at import time.
+    pub min_value: u64,
+}
+
+/// Caps on how many proposals the queue will hold.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct QueueLimits {
+    pub max_total: usize,
+    pub max_per_sender: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiSigTransactionQueue {
+    entries: Vec<MultiSigTransaction>,
+    limits: QueueLimits,
+    verifier: VerifierOptions,
+}
+
+impl MultiSigTransactionQueue {
+    pub fn new(limits: QueueLimits, verifier: VerifierOptions) -> Self {
+        MultiSigTransactionQueue { entries: Vec::new(), limits, verifier }
+    }
+
+    /// Updates the signer set / threshold / minimum value the queue
+    /// verifies new imports against. Entries already admitted are left
+    /// alone — this only affects what's accepted from here on.
+    pub fn set_verifier_options(&mut self, verifier: VerifierOptions) {
+        self.verifier = verifier;
+    }
+
+    fn verify(&self, transaction: &MultiSigTransaction) -> Result<()> {
+        if !self.verifier.valid_signers.contains(&transaction.proposer) {
+            return Err(anyhow!("proposer {} is not a current signer", transaction.proposer));
+        }
+        for signer in transaction.signatures.keys() {
+            if !self.verifier.valid_signers.contains(signer) {
+                return Err(anyhow!("signature from {} is not a current signer", signer));
+            }
+        }
+        if transaction.signatures.len() > self.verifier.threshold {
+            return Err(anyhow!(
+                "transaction carries {} signatures, more than the threshold of {}",
+                transaction.signatures.len(),
+                self.verifier.threshold
+            ));
+        }
+        if transaction.amount < self.verifier.min_value {
+            return Err(anyhow!(
+                "amount {} is below the minimum value {}",
+                transaction.amount,
+                self.verifier.min_value
+            ));
+        }
+        if self
+            .entries
+            .iter()
+            .any(|existing| existing.proposer == transaction.proposer && existing.sequence == transaction.sequence)
+        {
+            return Err(anyhow!(
+                "sequence {} is already in use by proposer {}",
+                transaction.sequence,
+                transaction.proposer
+            ));
+        }
+        Ok(())
+    }
+
+    /// Higher tuples survive eviction: a transaction further along on
+    /// signatures outranks one with fewer, and within that, a larger
+    /// amount outranks a smaller one.
+    fn priority(transaction: &MultiSigTransaction) -> (usize, u64) {
+        (transaction.signatures.len(), transaction.amount)
+    }
+
+    /// Evicts the lowest-priority entry among those from `sender` (or the
+    /// whole queue, if `sender` is `None`), but only if it's actually
+    /// lower priority than `incoming`. Returns whether an entry was evicted.
+    fn evict_lowest(&mut self, sender: Option<&str>, incoming: (usize, u64)) -> bool {
+        let lowest = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, transaction)| sender.map_or(true, |s| transaction.proposer == s))
+            .min_by_key(|(_, transaction)| Self::priority(transaction))
+            .map(|(index, transaction)| (index, Self::priority(transaction)));
+
+        match lowest {
+            Some((index, lowest_priority)) if lowest_priority < incoming => {
+                self.entries.remove(index);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Verifies and admits a single proposal, evicting the lowest-priority
+    /// entry to make room if a limit is already saturated and `transaction`
+    /// outranks it. Rejects `transaction` instead if it doesn't.
+    pub fn import(&mut self, transaction: MultiSigTransaction) -> Result<()> {
+        self.verify(&transaction)?;
+        let incoming_priority = Self::priority(&transaction);
+
+        let per_sender_count = self.entries.iter().filter(|t| t.proposer == transaction.proposer).count();
+        if per_sender_count >= self.limits.max_per_sender
+            && !self.evict_lowest(Some(&transaction.proposer), incoming_priority)
+        {
+            return Err(anyhow!(
+                "proposer {} already has {} queued transactions",
+                transaction.proposer,
+                self.limits.max_per_sender
+            ));
+        }
+
+        if self.entries.len() >= self.limits.max_total && !self.evict_lowest(None, incoming_priority) {
+            return Err(anyhow!("transaction queue is full ({} entries)", self.limits.max_total));
+        }
+
+        self.entries.push(transaction);
+        Ok(())
+    }
+
+    /// Imports a batch of proposals independently — one rejection doesn't
+    /// block the rest — returning each transaction's admission result in order.
+    pub fn import_batch(&mut self, transactions: Vec<MultiSigTransaction>) -> Vec<Result<()>> {
+        transactions.into_iter().map(|transaction| self.import(transaction)).collect()
+    }
+
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut MultiSigTransaction> {
+        self.entries.iter_mut().find(|transaction| transaction.id == id)
+    }
+
+    /// Removes the entry with `id`, whatever its state.
+    /// `MultiSigWallet::prune_expired` uses this to drop pending proposals
+    /// past their expiry; `import` separately evicts lower-priority entries
+    /// when a limit is full.
+    pub fn remove(&mut self, id: &str) -> Option<MultiSigTransaction> {
+        let index = self.entries.iter().position(|transaction| transaction.id == id)?;
+        Some(self.entries.remove(index))
+    }
+
+    pub fn all_transactions(&self) -> Vec<MultiSigTransaction> {
+        self.entries.clone()
+    }
+
+    /// Transactions that have both cleared the approval threshold and
+    /// reached the front of their proposer's sequence — i.e. genuinely
+    /// executable right now, as opposed to merely signed-enough.
+    pub fn ready(&self) -> Vec<MultiSigTransaction> {
+        self.entries
+            .iter()
+            .filter(|transaction| transaction.signatures.len() >= self.verifier.threshold && self.is_next_in_line(transaction))
+            .cloned()
+            .collect()
+    }
+
+    fn is_next_in_line(&self, transaction: &MultiSigTransaction) -> bool {
+        !self
+            .entries
+            .iter()
+            .any(|other| other.proposer == transaction.proposer && other.sequence < transaction.sequence)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, MultiSigTransaction> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -0,0 +1,160 @@
+//! A pluggable source of USD exchange rates, replacing the hardcoded
+//! table `get_total_balance_usd` used to carry. `StaticOracle` keeps
+//! that fixed table as a dependency-free default; `HttpPriceOracle`
+//! fetches live quotes from a price feed and caches the last good rate
+//! per currency (with its fetch timestamp), so a feed outage degrades
+//! to stale-but-usable data instead of a broken portfolio view.
+
+use crate::wallet::Currency;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+const ALL_CURRENCIES: [Currency; 6] = [
+    Currency::BTC,
+    Currency::ETH,
+    Currency::USDC,
+    Currency::LTC,
+    Currency::XMR,
+    Currency::DOGE,
+];
+
+/// A source of USD exchange rates for wallet valuation and cost-basis
+/// tracking. `rate`/`rates` are synchronous so `get_total_balance_usd`
+/// can consult them without an async context; implementations that need
+/// network I/O should do it out-of-band (see `HttpPriceOracle::refresh`)
+/// and serve whatever they last fetched.
+#[async_trait]
+pub trait PriceOracle: Send + Sync {
+    fn rate(&self, currency: Currency) -> Result<f64>;
+    fn rates(&self) -> HashMap<Currency, f64>;
+    /// The USD rate as of `unix_ts`, for per-transaction cost basis.
+    async fn historical_price(&self, currency: Currency, unix_ts: u64) -> Result<f64>;
+}
+
+/// Today's behavior: a fixed table of USD rates, good enough for demos
+/// and tests but not a real valuation source.
+pub struct StaticOracle {
+    rates: HashMap<Currency, f64>,
+}
+
+impl Default for StaticOracle {
+    fn default() -> Self {
+        StaticOracle {
+            rates: HashMap::from([
+                (Currency::BTC, 45000.0),
+                (Currency::ETH, 3000.0),
+                (Currency::USDC, 1.0),
+                (Currency::LTC, 150.0),
+                (Currency::XMR, 200.0),
+                (Currency::DOGE, 0.08),
+            ]),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceOracle for StaticOracle {
+    fn rate(&self, currency: Currency) -> Result<f64> {
+        self.rates
+            .get(&currency)
+            .copied()
+            .ok_or_else(|| anyhow!("no static rate for {}", currency.symbol()))
+    }
+
+    fn rates(&self) -> HashMap<Currency, f64> {
+        self.rates.clone()
+    }
+
+    async fn historical_price(&self, currency: Currency, _unix_ts: u64) -> Result<f64> {
+        // No time series to draw on; today's fixed rate is the best a
+        // static table can offer for any point in history.
+        self.rate(currency)
+    }
+}
+
+struct CachedRate {
+    usd: f64,
+    fetched_at: u64,
+}
+
+/// Fetches quotes from an HTTP price feed returning `{"usd": <number>}`
+/// per currency, caching the last good quote so `rate`/`rates` keep
+/// serving stale-but-valid data through a feed outage.
+pub struct HttpPriceOracle {
+    client: reqwest::Client,
+    base_url: String,
+    cache: RwLock<HashMap<Currency, CachedRate>>,
+}
+
+impl HttpPriceOracle {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        HttpPriceOracle {
+            client: reqwest::Client::new(),
+            base_url: base_url.into(),
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Fetches a fresh quote for every supported currency and updates
+    /// the cache. A currency whose fetch fails simply keeps its
+    /// previous cached value (if any) rather than being cleared.
+    pub async fn refresh(&self) -> Result<()> {
+        for currency in ALL_CURRENCIES {
+            let url = format!("{}/price/{}", self.base_url, currency.symbol());
+            let fetched = async {
+                let response = self.client.get(&url).send().await?;
+                let body: serde_json::Value = response.json().await?;
+                body["usd"]
+                    .as_f64()
+                    .ok_or_else(|| anyhow!("price feed response for {} missing \"usd\" field", currency.symbol()))
+            }
+            .await;
+
+            match fetched {
+                Ok(usd) => {
+                    self.cache.write().unwrap().insert(
+                        currency,
+                        CachedRate { usd, fetched_at: crate::core::data_structures::get_current_timestamp() },
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to refresh {} rate, keeping cached value: {}", currency.symbol(), e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// When the cached rate for `currency` was last successfully
+    /// fetched, if any.
+    pub fn fetched_at(&self, currency: Currency) -> Option<u64> {
+        self.cache.read().unwrap().get(&currency).map(|cached| cached.fetched_at)
+    }
+}
+
+#[async_trait]
+impl PriceOracle for HttpPriceOracle {
+    fn rate(&self, currency: Currency) -> Result<f64> {
+        self.cache
+            .read()
+            .unwrap()
+            .get(&currency)
+            .map(|cached| cached.usd)
+            .ok_or_else(|| anyhow!("no cached rate for {}; call refresh() first", currency.symbol()))
+    }
+
+    fn rates(&self) -> HashMap<Currency, f64> {
+        self.cache.read().unwrap().iter().map(|(currency, cached)| (*currency, cached.usd)).collect()
+    }
+
+    async fn historical_price(&self, currency: Currency, unix_ts: u64) -> Result<f64> {
+        let url = format!("{}/price/{}/history", self.base_url, currency.symbol());
+        let response = self.client.get(&url).query(&[("timestamp", unix_ts)]).send().await?;
+        let body: serde_json::Value = response.json().await?;
+        body["usd"]
+            .as_f64()
+            .ok_or_else(|| anyhow!("historical price response for {} missing \"usd\" field", currency.symbol()))
+    }
+}
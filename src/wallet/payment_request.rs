@@ -0,0 +1,325 @@
+//! Canonical "pay me" strings for the receive flow. A `PaymentRequest`
+//! bundles an address with an optional amount and memo and renders to a
+//! BIP21-style URI (an ERC-681-style form for the ERC-20 `USDC` token),
+//! plus the same payload as a scannable QR code. `parse_uri` is the
+//! inverse, so a scanned request can prefill `SendRequest`.
+
+use crate::wallet::{Currency, SendRequest};
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Mainnet USDC contract address, used for the ERC-681 `/transfer` form.
+const USDC_CONTRACT_ADDRESS: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentRequest {
+    pub currency: Currency,
+    pub address: String,
+    pub amount: Option<u64>,
+    pub memo: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentRequestPayload {
+    pub uri: String,
+    /// `data:image/svg+xml;base64,<...>` — embeddable directly in an
+    /// `<img>` tag without a separate asset round-trip.
+    pub qr_data_uri: String,
+}
+
+/// Expected leading characters of an address `Wallet::generate_address`
+/// would produce for `currency`, used to sanity-check a parsed `duxnet:`
+/// URI before it's trusted as a `SendRequest`.
+fn address_prefix(currency: Currency) -> &'static str {
+    match currency {
+        Currency::BTC => "1",
+        Currency::ETH | Currency::USDC => "0x",
+        Currency::LTC => "L",
+        Currency::XMR => "4",
+        Currency::DOGE => "D",
+    }
+}
+
+fn currency_for_symbol(symbol: &str) -> Option<Currency> {
+    match symbol {
+        "BTC" => Some(Currency::BTC),
+        "ETH" => Some(Currency::ETH),
+        "USDC" => Some(Currency::USDC),
+        "LTC" => Some(Currency::LTC),
+        "XMR" => Some(Currency::XMR),
+        "DOGE" => Some(Currency::DOGE),
+        _ => None,
+    }
+}
+
+impl PaymentRequest {
+    /// Renders this request as a single shareable `duxnet:` URI, e.g.
+    /// `duxnet:0xabc...?currency=ETH&amount=1.5&memo=coffee`,
+    /// so a payee can hand out one string instead of raw address/amount/
+    /// memo fields. Unlike `encode_uri`, this form always carries an
+    /// explicit `currency` parameter and round-trips straight to a
+    /// `SendRequest` via `parse`. It never emits a `fee` parameter;
+    /// `parse` accepts one when present.
+    pub fn to_uri(&self) -> Result<String> {
+        let mut query: Vec<(String, String)> =
+            vec![("currency".to_string(), self.currency.symbol().to_string())];
+        if let Some(amount) = self.amount {
+            let whole = crate::wallet::rate::to_whole_units(self.currency, amount)?;
+            query.push(("amount".to_string(), whole.normalize().to_string()));
+        }
+        if let Some(memo) = &self.memo {
+            query.push(("memo".to_string(), urlencode(memo)));
+        }
+        Ok(format!("duxnet:{}{}", self.address, render_query(&query)))
+    }
+
+    /// Parses a `duxnet:` URI produced by `to_uri` back into a
+    /// `SendRequest`. Validates that `address` carries the prefix
+    /// `Wallet::generate_address` would have produced for the URI's
+    /// `currency`, and converts the human-decimal `amount` into
+    /// `currency`'s smallest units.
+    pub fn parse(uri: &str) -> Result<SendRequest> {
+        let rest = uri
+            .strip_prefix("duxnet:")
+            .ok_or_else(|| anyhow!("not a duxnet payment URI"))?;
+        let (address, query) = match rest.split_once('?') {
+            Some((address, query)) => (address.to_string(), parse_query(query)),
+            None => (rest.to_string(), HashMap::new()),
+        };
+
+        let currency_symbol = query
+            .get("currency")
+            .ok_or_else(|| anyhow!("payment URI missing currency"))?;
+        let currency = currency_for_symbol(currency_symbol)
+            .ok_or_else(|| anyhow!("unrecognized currency: {}", currency_symbol))?;
+
+        let prefix = address_prefix(currency);
+        if !address.starts_with(prefix) {
+            return Err(anyhow!(
+                "address \"{}\" does not match {}'s \"{}\" prefix",
+                address,
+                currency.symbol(),
+                prefix
+            ));
+        }
+
+        let amount = query
+            .get("amount")
+            .map(|raw| {
+                raw.parse::<rust_decimal::Decimal>()
+                    .map_err(|e| anyhow!("invalid amount: {}", e))
+                    .and_then(|whole| crate::wallet::rate::from_whole_units(currency, whole))
+            })
+            .transpose()?
+            .unwrap_or(0);
+
+        let fee = query
+            .get("fee")
+            .map(|raw| {
+                raw.parse::<rust_decimal::Decimal>()
+                    .map_err(|e| anyhow!("invalid fee: {}", e))
+                    .and_then(|whole| crate::wallet::rate::from_whole_units(currency, whole))
+            })
+            .transpose()?;
+
+        Ok(SendRequest {
+            to_address: address,
+            amount,
+            currency,
+            memo: query.get("memo").map(|s| urldecode(s)),
+            fee,
+        })
+    }
+}
+
+fn scheme_for(currency: Currency) -> &'static str {
+    match currency {
+        Currency::BTC => "bitcoin",
+        Currency::ETH => "ethereum",
+        Currency::USDC => "ethereum",
+        Currency::LTC => "litecoin",
+        Currency::XMR => "monero",
+        Currency::DOGE => "dogecoin",
+    }
+}
+
+/// Encodes `request` as a BIP21-style URI, e.g.
+/// `bitcoin:bc1q...?amount=0.001&label=coffee`. `USDC` instead takes the
+/// ERC-681 `/transfer` form so wallets route it through the token
+/// contract rather than a plain ETH transfer.
+pub fn encode_uri(request: &PaymentRequest) -> Result<String> {
+    let scheme = scheme_for(request.currency);
+
+    let mut query: Vec<(String, String)> = Vec::new();
+    if let Some(amount) = request.amount {
+        let whole = crate::wallet::rate::to_whole_units(request.currency, amount)?;
+        query.push(("amount".to_string(), whole.normalize().to_string()));
+    }
+    if let Some(memo) = &request.memo {
+        query.push(("label".to_string(), urlencode(memo)));
+    }
+
+    if request.currency == Currency::USDC {
+        query.insert(0, ("address".to_string(), request.address.clone()));
+        if let Some(amount) = request.amount {
+            query.push(("uint256".to_string(), amount.to_string()));
+        }
+        let query_string = render_query(&query);
+        return Ok(format!(
+            "{}:{}/transfer{}",
+            scheme, USDC_CONTRACT_ADDRESS, query_string
+        ));
+    }
+
+    let query_string = render_query(&query);
+    Ok(format!("{}:{}{}", scheme, request.address, query_string))
+}
+
+/// Decodes a URI produced by `encode_uri` back into a `PaymentRequest`.
+pub fn parse_uri(uri: &str) -> Result<PaymentRequest> {
+    let (scheme, rest) = uri
+        .split_once(':')
+        .ok_or_else(|| anyhow!("not a payment URI: missing scheme"))?;
+
+    let (path, query) = match rest.split_once('?') {
+        Some((path, query)) => (path, parse_query(query)),
+        None => (rest, HashMap::new()),
+    };
+
+    if path.ends_with("/transfer") {
+        let contract = path.trim_end_matches("/transfer");
+        if contract.eq_ignore_ascii_case(USDC_CONTRACT_ADDRESS) && scheme == "ethereum" {
+            let address = query
+                .get("address")
+                .ok_or_else(|| anyhow!("USDC payment URI missing address"))?
+                .clone();
+            let amount = query
+                .get("uint256")
+                .map(|raw| {
+                    raw.parse::<u64>()
+                        .map_err(|e| anyhow!("invalid uint256 amount: {}", e))
+                })
+                .transpose()?;
+            return Ok(PaymentRequest {
+                currency: Currency::USDC,
+                address,
+                amount,
+                memo: query.get("label").map(|s| urldecode(s)),
+            });
+        }
+        return Err(anyhow!("unrecognized token transfer URI: {}", uri));
+    }
+
+    let currency = currency_for_scheme(scheme)
+        .ok_or_else(|| anyhow!("unrecognized payment URI scheme: {}", scheme))?;
+
+    let amount = query
+        .get("amount")
+        .map(|raw| {
+            raw.parse::<rust_decimal::Decimal>()
+                .map_err(|e| anyhow!("invalid amount: {}", e))
+                .and_then(|whole| crate::wallet::rate::from_whole_units(currency, whole))
+        })
+        .transpose()?;
+
+    Ok(PaymentRequest {
+        currency,
+        address: path.to_string(),
+        amount,
+        memo: query.get("label").map(|s| urldecode(s)),
+    })
+}
+
+fn currency_for_scheme(scheme: &str) -> Option<Currency> {
+    match scheme {
+        "bitcoin" => Some(Currency::BTC),
+        "ethereum" => Some(Currency::ETH),
+        "litecoin" => Some(Currency::LTC),
+        "monero" => Some(Currency::XMR),
+        "dogecoin" => Some(Currency::DOGE),
+        _ => None,
+    }
+}
+
+fn render_query(pairs: &[(String, String)]) -> String {
+    if pairs.is_empty() {
+        return String::new();
+    }
+    let joined = pairs
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("?{}", joined)
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (k.to_string(), urldecode(v)))
+        .collect()
+}
+
+fn urlencode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+fn urldecode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                if let Ok(byte) = u8::from_str_radix(&value[i + 1..i + 3], 16) {
+                    out.push(byte);
+                    i += 3;
+                    continue;
+                }
+                out.push(bytes[i]);
+                i += 1;
+            }
+            b'+' => {
+                out.push(b' ');
+                i += 1;
+            }
+            b => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Renders `uri` as a QR code SVG wrapped in a `data:` URI, suitable for
+/// an `<img src>` without a separate asset fetch.
+pub fn to_qr_data_uri(uri: &str) -> Result<String> {
+    let code = qrcode::QrCode::new(uri.as_bytes())
+        .map_err(|e| anyhow!("failed to encode payment URI as a QR code: {}", e))?;
+    let svg = code
+        .render::<qrcode::render::svg::Color>()
+        .min_dimensions(256, 256)
+        .build();
+    let encoded = general_purpose::STANDARD.encode(svg.as_bytes());
+    Ok(format!("data:image/svg+xml;base64,{}", encoded))
+}
+
+/// Builds the full `PaymentRequestPayload` (URI + QR) for `request`.
+pub fn build_payload(request: &PaymentRequest) -> Result<PaymentRequestPayload> {
+    let uri = encode_uri(request)?;
+    let qr_data_uri = to_qr_data_uri(&uri)?;
+    Ok(PaymentRequestPayload { uri, qr_data_uri })
+}
@@ -0,0 +1,155 @@
+//! Decimal-precise currency valuation. A pluggable `PriceProvider` feeds
+//! USD quotes into a TTL-cached `RateCache`, and every conversion between
+//! a currency's smallest-unit balances and USD goes through
+//! `rust_decimal::Decimal` so financial paths never round through floats.
+
+use crate::wallet::Currency;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::debug;
+
+const DEFAULT_TTL_SECS: u64 = 60;
+
+/// A USD quote for one currency, as of `fetched_at`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rate {
+    pub currency: Currency,
+    pub usd_per_unit: Decimal,
+    pub fetched_at: u64,
+}
+
+#[async_trait]
+pub trait PriceProvider: Send + Sync {
+    async fn fetch_rate(&self, currency: Currency) -> Result<Decimal>;
+}
+
+/// Fetches quotes from an HTTP price feed returning `{"usd": "<decimal>"}`
+/// per currency.
+pub struct HttpPriceProvider {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl HttpPriceProvider {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        HttpPriceProvider {
+            client: reqwest::Client::new(),
+            base_url: base_url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceProvider for HttpPriceProvider {
+    async fn fetch_rate(&self, currency: Currency) -> Result<Decimal> {
+        let url = format!("{}/price/{}", self.base_url, currency.symbol());
+        let response = self.client.get(&url).send().await?;
+        let body: serde_json::Value = response.json().await?;
+        let price_str = body["usd"]
+            .as_str()
+            .ok_or_else(|| anyhow!("price feed response for {} missing \"usd\" field", currency.symbol()))?;
+        price_str
+            .parse::<Decimal>()
+            .map_err(|e| anyhow!("invalid price quote for {}: {}", currency.symbol(), e))
+    }
+}
+
+/// Caches `Rate`s fetched from a `PriceProvider` for `ttl_secs`, so every
+/// balance or escrow valuation doesn't round-trip to the price feed.
+pub struct RateCache {
+    provider: Arc<dyn PriceProvider>,
+    ttl_secs: u64,
+    rates: Arc<RwLock<HashMap<Currency, Rate>>>,
+}
+
+impl RateCache {
+    pub fn new(provider: Arc<dyn PriceProvider>) -> Self {
+        Self::with_ttl(provider, DEFAULT_TTL_SECS)
+    }
+
+    pub fn with_ttl(provider: Arc<dyn PriceProvider>, ttl_secs: u64) -> Self {
+        RateCache {
+            provider,
+            ttl_secs,
+            rates: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub async fn get_rate(&self, currency: Currency) -> Result<Rate> {
+        let now = crate::core::data_structures::get_current_timestamp();
+        if let Some(rate) = self.rates.read().await.get(&currency) {
+            if now < rate.fetched_at + self.ttl_secs {
+                return Ok(rate.clone());
+            }
+        }
+
+        let usd_per_unit = self.provider.fetch_rate(currency).await?;
+        let rate = Rate { currency, usd_per_unit, fetched_at: now };
+        self.rates.write().await.insert(currency, rate.clone());
+        debug!("Refreshed {} rate: {} USD", currency.symbol(), usd_per_unit);
+        Ok(rate)
+    }
+
+    pub async fn get_all_rates(&self) -> Result<Vec<Rate>> {
+        let mut rates = Vec::new();
+        for currency in [Currency::BTC, Currency::ETH, Currency::USDC, Currency::LTC, Currency::XMR, Currency::DOGE] {
+            rates.push(self.get_rate(currency).await?);
+        }
+        Ok(rates)
+    }
+}
+
+/// Converts `amount` (in `currency`'s smallest units) to a decimal "whole
+/// units" value, e.g. satoshis to BTC, without floating point.
+pub fn to_whole_units(currency: Currency, amount: u64) -> Result<Decimal> {
+    let scale = Decimal::from(
+        10u64
+            .checked_pow(currency.decimals() as u32)
+            .ok_or_else(|| anyhow!("{} scale overflow", currency.symbol()))?,
+    );
+    Decimal::from(amount)
+        .checked_div(scale)
+        .ok_or_else(|| anyhow!("overflow converting {} to whole units", currency.symbol()))
+}
+
+/// Converts a decimal "whole units" value back to `currency`'s smallest
+/// units, rounding to the nearest unit.
+pub fn from_whole_units(currency: Currency, amount: Decimal) -> Result<u64> {
+    let scale = Decimal::from(
+        10u64
+            .checked_pow(currency.decimals() as u32)
+            .ok_or_else(|| anyhow!("{} scale overflow", currency.symbol()))?,
+    );
+    let smallest_units = amount
+        .checked_mul(scale)
+        .ok_or_else(|| anyhow!("overflow converting {} from whole units", currency.symbol()))?;
+    smallest_units
+        .round()
+        .to_u64()
+        .ok_or_else(|| anyhow!("{} amount does not fit in a u64", currency.symbol()))
+}
+
+/// Converts `amount` (smallest units of `currency`) to USD using `rate`.
+pub fn to_usd(currency: Currency, amount: u64, rate: &Rate) -> Result<Decimal> {
+    let whole_units = to_whole_units(currency, amount)?;
+    whole_units
+        .checked_mul(rate.usd_per_unit)
+        .ok_or_else(|| anyhow!("overflow converting {} to USD", currency.symbol()))
+}
+
+/// Converts a USD amount to `currency`'s smallest units using `rate`.
+pub fn from_usd(currency: Currency, usd_amount: Decimal, rate: &Rate) -> Result<u64> {
+    if rate.usd_per_unit.is_zero() {
+        return Err(anyhow!("{} has no usable rate", currency.symbol()));
+    }
+    let whole_units = usd_amount
+        .checked_div(rate.usd_per_unit)
+        .ok_or_else(|| anyhow!("overflow converting USD to {}", currency.symbol()))?;
+    from_whole_units(currency, whole_units)
+}
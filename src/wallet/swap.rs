@@ -0,0 +1,250 @@
+//! Trustless cross-currency atomic swaps between two `Wallet` holders,
+//! entirely inside the mock ledger (no custodian, no escrow contract).
+//!
+//! The initiator picks a random 32-byte preimage `s`, hashes it to `H`,
+//! and locks `from_amount` of `from_currency` under `H` with refund
+//! timeout `timeout_initiator`. Seeing `H`, the counterparty locks
+//! `to_amount` of `to_currency` under the same `H` with a strictly
+//! shorter `timeout_counterparty`, so the initiator can never redeem
+//! both legs and vanish before the counterparty can react. The initiator
+//! redeems the counterparty's leg by revealing `s`; the counterparty
+//! then uses the now-public `s` to redeem the initiator's leg. Either
+//! leg refunds to its locker once its own timelock has passed unredeemed.
+
+use crate::wallet::{Currency, Transaction, TransactionStatus, Wallet};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SwapState {
+    /// Initiator has locked their leg; waiting on the counterparty.
+    InitiatorLocked,
+    /// Both legs are locked; either side may now redeem or, after its
+    /// own timelock, refund.
+    BothLocked,
+    /// The initiator has revealed `s` and redeemed the counterparty's leg.
+    InitiatorRedeemed,
+    /// The counterparty has used the revealed `s` to redeem the
+    /// initiator's leg; the swap is complete.
+    Completed,
+    Refunded,
+}
+
+/// Which side of the swap an operation applies to, since each party only
+/// ever touches their own local `Wallet`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapLeg {
+    Initiator,
+    Counterparty,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapContract {
+    pub id: String,
+    pub initiator_did: String,
+    pub counterparty_did: String,
+    pub from_currency: Currency,
+    pub from_amount: u64,
+    pub to_currency: Currency,
+    pub to_amount: u64,
+    #[serde(with = "hex_bytes")]
+    pub hash: [u8; 32],
+    pub timeout_initiator: u64,
+    pub timeout_counterparty: Option<u64>,
+    pub initiator_lock_tx: Option<String>,
+    pub counterparty_lock_tx: Option<String>,
+    pub state: SwapState,
+    pub created_at: u64,
+}
+
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let bytes = hex::decode(s).map_err(serde::de::Error::custom)?;
+        bytes.try_into().map_err(|_| serde::de::Error::custom("expected 32 bytes"))
+    }
+}
+
+impl SwapContract {
+    /// Starts a new swap: generates the secret preimage and derives the
+    /// shared hashlock. The preimage is returned alongside the contract
+    /// so the initiator can hold onto it until they're ready to redeem —
+    /// it is never stored on the contract itself.
+    pub fn new(
+        initiator_did: String,
+        counterparty_did: String,
+        from_currency: Currency,
+        from_amount: u64,
+        to_currency: Currency,
+        to_amount: u64,
+        timeout_initiator_secs: u64,
+    ) -> (Self, [u8; 32]) {
+        let preimage: [u8; 32] = rand::random();
+        let hash: [u8; 32] = Sha256::digest(preimage).into();
+
+        let contract = SwapContract {
+            id: uuid::Uuid::new_v4().to_string(),
+            initiator_did,
+            counterparty_did,
+            from_currency,
+            from_amount,
+            to_currency,
+            to_amount,
+            hash,
+            timeout_initiator: crate::core::data_structures::get_current_timestamp() + timeout_initiator_secs,
+            timeout_counterparty: None,
+            initiator_lock_tx: None,
+            counterparty_lock_tx: None,
+            state: SwapState::InitiatorLocked,
+            created_at: crate::core::data_structures::get_current_timestamp(),
+        };
+        (contract, preimage)
+    }
+
+    fn lock_address(&self) -> String {
+        format!("swap:{}", self.id)
+    }
+
+    /// Locks the initiator's `from_amount` of `from_currency` out of
+    /// `wallet`, keyed by the shared hashlock.
+    pub fn lock_initiator(&mut self, wallet: &mut Wallet) -> Result<()> {
+        if self.initiator_lock_tx.is_some() {
+            return Err(anyhow!("initiator leg already locked"));
+        }
+        let tx = self.lock_leg(wallet, self.from_currency, self.from_amount, self.timeout_initiator)?;
+        self.initiator_lock_tx = Some(tx);
+        Ok(())
+    }
+
+    /// Locks the counterparty's `to_amount` of `to_currency` out of
+    /// `wallet`. `timeout_counterparty` must be strictly shorter than
+    /// `timeout_initiator`, so the initiator can never hold both legs
+    /// past the point where the counterparty can still react.
+    pub fn lock_counterparty(&mut self, wallet: &mut Wallet, timeout_counterparty: u64) -> Result<()> {
+        if self.state != SwapState::InitiatorLocked || self.initiator_lock_tx.is_none() {
+            return Err(anyhow!("counterparty leg can only be locked after the initiator's"));
+        }
+        if timeout_counterparty >= self.timeout_initiator {
+            return Err(anyhow!(
+                "counterparty timeout must be shorter than the initiator's refund timeout"
+            ));
+        }
+        let tx = self.lock_leg(wallet, self.to_currency, self.to_amount, timeout_counterparty)?;
+        self.counterparty_lock_tx = Some(tx);
+        self.timeout_counterparty = Some(timeout_counterparty);
+        self.state = SwapState::BothLocked;
+        Ok(())
+    }
+
+    fn lock_leg(&self, wallet: &mut Wallet, currency: Currency, amount: u64, timelock: u64) -> Result<String> {
+        wallet.remove_funds(&currency, amount)?;
+
+        let transaction = Transaction {
+            id: uuid::Uuid::new_v4().to_string(),
+            from: wallet.did.clone(),
+            to: self.lock_address(),
+            amount,
+            currency,
+            timestamp: crate::core::data_structures::get_current_timestamp(),
+            signature: Vec::new(),
+            status: TransactionStatus::Pending,
+            fee: 0,
+            block_height: None,
+            confirmations: 0,
+            memo: Some(format!("atomic swap {} lock", self.id)),
+            hashlock: Some(self.hash),
+            timelock: Some(timelock),
+            revealed_preimage: None,
+        };
+        let tx_id = transaction.id.clone();
+        wallet.transactions.push(transaction);
+        wallet.last_activity = crate::core::data_structures::get_current_timestamp();
+        Ok(tx_id)
+    }
+
+    /// Claims `leg`'s locked funds by revealing `preimage`. The
+    /// initiator calls this with `SwapLeg::Counterparty` to claim
+    /// `to_currency`; once `preimage` is public, the counterparty calls
+    /// it with `SwapLeg::Initiator` to claim `from_currency`.
+    pub fn redeem(&mut self, wallet: &mut Wallet, leg: SwapLeg, preimage: [u8; 32]) -> Result<()> {
+        let computed: [u8; 32] = Sha256::digest(preimage).into();
+        if computed != self.hash {
+            return Err(anyhow!("preimage does not match the swap's hashlock"));
+        }
+
+        let (currency, amount, timelock, lock_tx_id) = match leg {
+            SwapLeg::Counterparty => (
+                self.to_currency,
+                self.to_amount,
+                self.timeout_counterparty.ok_or_else(|| anyhow!("counterparty leg is not locked yet"))?,
+                self.counterparty_lock_tx.clone().ok_or_else(|| anyhow!("counterparty leg is not locked yet"))?,
+            ),
+            SwapLeg::Initiator => (
+                self.from_currency,
+                self.from_amount,
+                self.timeout_initiator,
+                self.initiator_lock_tx.clone().ok_or_else(|| anyhow!("initiator leg is not locked yet"))?,
+            ),
+        };
+
+        if crate::core::data_structures::get_current_timestamp() > timelock {
+            return Err(anyhow!("leg's timelock has expired; use refund instead"));
+        }
+
+        wallet.add_funds(currency, amount);
+        if let Some(tx) = wallet.transactions.iter_mut().find(|t| t.id == lock_tx_id) {
+            tx.revealed_preimage = Some(preimage);
+            tx.status = TransactionStatus::Confirmed;
+        }
+
+        self.state = match leg {
+            SwapLeg::Counterparty => SwapState::InitiatorRedeemed,
+            SwapLeg::Initiator => SwapState::Completed,
+        };
+        Ok(())
+    }
+
+    /// Reclaims `leg`'s locked funds once its timelock has passed
+    /// unredeemed, for whichever party locked that leg.
+    pub fn refund(&mut self, wallet: &mut Wallet, leg: SwapLeg) -> Result<()> {
+        let (currency, amount, timelock, lock_tx_id) = match leg {
+            SwapLeg::Initiator => (
+                self.from_currency,
+                self.from_amount,
+                self.timeout_initiator,
+                self.initiator_lock_tx.clone().ok_or_else(|| anyhow!("initiator leg is not locked"))?,
+            ),
+            SwapLeg::Counterparty => (
+                self.to_currency,
+                self.to_amount,
+                self.timeout_counterparty.ok_or_else(|| anyhow!("counterparty leg is not locked"))?,
+                self.counterparty_lock_tx.clone().ok_or_else(|| anyhow!("counterparty leg is not locked"))?,
+            ),
+        };
+
+        if crate::core::data_structures::get_current_timestamp() <= timelock {
+            return Err(anyhow!("leg's timelock has not expired yet"));
+        }
+        let redeemed = match leg {
+            SwapLeg::Counterparty => matches!(self.state, SwapState::InitiatorRedeemed | SwapState::Completed),
+            SwapLeg::Initiator => self.state == SwapState::Completed,
+        };
+        if redeemed {
+            return Err(anyhow!("leg has already been redeemed"));
+        }
+
+        wallet.add_funds(currency, amount);
+        if let Some(tx) = wallet.transactions.iter_mut().find(|t| t.id == lock_tx_id) {
+            tx.status = TransactionStatus::Cancelled;
+        }
+        self.state = SwapState::Refunded;
+        Ok(())
+    }
+}